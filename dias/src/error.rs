@@ -0,0 +1,108 @@
+use std::error::Error;
+use std::fmt;
+
+use polars::prelude::PolarsError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug)]
+pub enum DiasError {
+    Polars(PolarsError),
+    InvalidParameter {
+        step: &'static str,
+        parameter: &'static str,
+        reason: String
+    },
+    Serialization(String),
+    UnsupportedVersion {
+        found: u32,
+        oldest: u32,
+        supported: u32
+    },
+    Validation(ValidationReport),
+    Query {
+        position: usize,
+        reason: String
+    },
+}
+
+pub type DiasResult<T> = Result<T, DiasError>;
+
+impl DiasError {
+    pub fn invalid_parameter(step: &'static str, parameter: &'static str, reason: impl Into<String>) -> DiasError {
+        DiasError::InvalidParameter { step, parameter, reason: reason.into() }
+    }
+}
+
+impl fmt::Display for DiasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiasError::Polars(err) => write!(f, "{err}"),
+            DiasError::InvalidParameter { step, parameter, reason } => {
+                write!(f, "invalid `{parameter}` in {step} step: {reason}")
+            },
+            DiasError::Serialization(reason) => write!(f, "invalid pipeline definition: {reason}"),
+            DiasError::UnsupportedVersion { found, oldest, supported } => {
+                write!(f, "unsupported pipeline definition version {found}, expected a version from {oldest} to {supported}")
+            },
+            DiasError::Validation(report) => write!(f, "validation failed: {report}"),
+            DiasError::Query { position, reason } => write!(f, "invalid filter query at position {position}: {reason}")
+        }
+    }
+}
+
+impl Error for DiasError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DiasError::Polars(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+impl From<PolarsError> for DiasError {
+    fn from(err: PolarsError) -> DiasError {
+        DiasError::Polars(err)
+    }
+}
+
+impl From<serde_json::Error> for DiasError {
+    fn from(err: serde_json::Error) -> DiasError {
+        DiasError::Serialization(err.to_string())
+    }
+}
+
+impl From<serde_yaml::Error> for DiasError {
+    fn from(err: serde_yaml::Error) -> DiasError {
+        DiasError::Serialization(err.to_string())
+    }
+}
+
+// Outcome of a Validate step, carried by DiasError::Validation when rows are rejected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleReport {
+    pub rule: String,
+    pub violations: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub rows: usize,
+    pub rejected_rows: usize,
+    pub rules: Vec<RuleReport>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.rejected_rows == 0
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} rows failed validation", self.rejected_rows, self.rows)?;
+        for rule in self.rules.iter().filter(|rule| rule.violations > 0) {
+            write!(f, "\n  - {}: {} rows", rule.rule, rule.violations)?;
+        }
+        Ok(())
+    }
+}
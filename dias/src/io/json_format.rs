@@ -0,0 +1,10 @@
+use polars::prelude::JsonFormat;
+use serde::{Deserialize, Serialize};
+
+// Serde definition for polars' JsonFormat, shared by the JSON source and sink
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "JsonFormat")]
+pub(crate) enum JsonFormatDef {
+    Json,
+    JsonLines,
+}
@@ -1 +1,3 @@
+mod json_format;
 pub mod read;
+pub mod write;
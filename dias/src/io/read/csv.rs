@@ -1,19 +1,173 @@
+use std::path::PathBuf;
+
+use polars::io::path_utils::expand_paths;
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use super::reader::Reader;
 
+fn comment_prefix(prefix: &CommentPrefix) -> PlSmallStr {
+    match prefix {
+        CommentPrefix::Single(c) => PlSmallStr::from((*c as char).to_string()),
+        CommentPrefix::Multi(s) => s.clone()
+    }
+}
+
+fn configure_scan(scan: LazyCsvReader, options: &CsvReadOptions) -> LazyCsvReader {
+    let parse_options = &options.parse_options;
+    scan.with_has_header(options.has_header)
+        .with_separator(parse_options.separator)
+        .with_quote_char(parse_options.quote_char)
+        .with_eol_char(parse_options.eol_char)
+        .with_encoding(parse_options.encoding)
+        .with_null_values(parse_options.null_values.clone())
+        .with_missing_is_null(parse_options.missing_is_null)
+        .with_truncate_ragged_lines(parse_options.truncate_ragged_lines)
+        .with_comment_prefix(parse_options.comment_prefix.as_ref().map(comment_prefix))
+        .with_try_parse_dates(parse_options.try_parse_dates)
+        .with_decimal_comma(parse_options.decimal_comma)
+        .with_low_memory(options.low_memory)
+        .with_n_rows(options.n_rows)
+        .with_row_index(options.row_index.clone())
+        .with_schema(options.schema.clone())
+        .with_dtype_overwrite(options.schema_overwrite.clone())
+        .with_skip_rows(options.skip_rows)
+        .with_skip_rows_after_header(options.skip_rows_after_header)
+        .with_infer_schema_length(options.infer_schema_length)
+        .with_raise_if_empty(options.raise_if_empty)
+        .with_ignore_errors(options.ignore_errors)
+}
+
+// Column selection and casts are left to the query plan so they can be pushed down into the scan
+fn project(mut df: LazyFrame, options: &CsvReadOptions) -> LazyFrame {
+    if options.columns.is_some() || options.projection.is_some() {
+        let mut selection = Vec::new();
+        if let Some(row_index) = &options.row_index {
+            selection.push(col(row_index.name.clone()));
+        }
+        if let Some(columns) = &options.columns {
+            selection.extend(columns.iter().map(|column| col(column.clone())));
+        }
+        if let Some(projection) = &options.projection {
+            selection.extend(projection.iter().map(|index| nth(*index as i64)));
+        }
+        df = df.select(selection);
+    }
+    if !options.fields_to_cast.is_empty() {
+        df = df.with_columns(
+            options.fields_to_cast
+                .iter()
+                .map(|field| col(field.name().clone()).cast(field.dtype().clone()))
+                .collect::<Vec<_>>()
+        );
+    }
+    df
+}
+
 impl Reader for CsvReadOptions {
     fn extract(self) -> PolarsResult<LazyFrame> {
-        Ok(self.try_into_reader_with_file_path(None)
-            ?.finish()
-            ?.lazy())
+        let Some(path) = &self.path else {
+            polars_bail!(ComputeError: "a path is required to scan a CSV file");
+        };
+        if self.dtype_overwrite.is_some() {
+            polars_bail!(InvalidOperation: "positional `dtype_overwrite` is not supported when scanning, use `schema_overwrite` instead");
+        }
+        let df = configure_scan(LazyCsvReader::new(path), &self).finish()?;
+        Ok(project(df, &self))
+    }
+}
+
+// How frames read from several files are stacked, mirrors the `how` argument of polars' concat
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Concat {
+    // All files must share the same schema
+    #[default]
+    Vertical,
+    // Columns with differing types are cast to their common supertype
+    VerticalRelaxed,
+    // Columns missing from a file are filled with nulls
+    Diagonal,
+    DiagonalRelaxed,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MultiCsvSource {
+    // Each entry may be a file or a glob pattern
+    pub paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub options: CsvReadOptions,
+    #[serde(default)]
+    pub concat: Concat,
+    // Name of a column recording the file each row was read from
+    #[serde(default)]
+    pub include_file_paths: Option<String>,
+}
+
+impl MultiCsvSource {
+    pub fn new(paths: Vec<PathBuf>) -> MultiCsvSource {
+        MultiCsvSource {
+            paths,
+            options: CsvReadOptions::default(),
+            concat: Concat::default(),
+            include_file_paths: None
+        }
+    }
+}
+
+impl Reader for MultiCsvSource {
+    fn extract(self) -> PolarsResult<LazyFrame> {
+        if self.options.dtype_overwrite.is_some() {
+            polars_bail!(InvalidOperation: "positional `dtype_overwrite` is not supported when scanning, use `schema_overwrite` instead");
+        }
+        let files = expand_paths(&self.paths, true, None)?;
+        if files.is_empty() {
+            polars_bail!(ComputeError: "no CSV files matched {:?}", self.paths);
+        }
+
+        // Files are scanned one by one so their schemas can be reconciled,
+        // the row index and the row limit have to be applied once they are stacked
+        let file_options = CsvReadOptions {
+            row_index: None,
+            n_rows: None,
+            ..self.options.clone()
+        };
+        let frames = files
+            .iter()
+            .map(|file| {
+                configure_scan(LazyCsvReader::new(file), &file_options)
+                    .with_include_file_paths(self.include_file_paths.as_deref().map(PlSmallStr::from))
+                    .finish()
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        let args = UnionArgs {
+            to_supertypes: matches!(self.concat, Concat::VerticalRelaxed | Concat::DiagonalRelaxed),
+            ..Default::default()
+        };
+        let mut df = match self.concat {
+            Concat::Vertical | Concat::VerticalRelaxed => concat(frames, args)?,
+            Concat::Diagonal | Concat::DiagonalRelaxed => concat_lf_diagonal(frames, args)?
+        };
+        if let Some(n_rows) = self.options.n_rows {
+            df = df.limit(n_rows as IdxSize);
+        }
+        if let Some(row_index) = &self.options.row_index {
+            df = df.with_row_index(row_index.name.clone(), Some(row_index.offset));
+        }
+
+        // The lineage column is kept when the options narrow down the columns
+        let mut options = self.options;
+        if let (Some(columns), Some(include_file_paths)) = (&options.columns, &self.include_file_paths) {
+            options.columns = Some(columns.iter().cloned().chain([PlSmallStr::from(include_file_paths.as_str())]).collect());
+        }
+        Ok(project(df, &options))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
+    use polars::io::RowIndex;
     use std::fs::File;
     use std::io::prelude::*;
     use tempfile::TempDir;
@@ -23,6 +177,7 @@ mod tests {
         _tempdir: TempDir,
     }
     impl Fixture {
+        #[allow(clippy::needless_borrows_for_generic_args)]
         fn blank(fixture_filename: &str) -> Self {
             let tempdir = tempfile::tempdir().unwrap();
             let mut path = PathBuf::from(&tempdir.path());
@@ -56,4 +211,122 @@ mod tests {
         assert_eq!(result.get_column_names(), col_names);
         assert!(!result.is_empty());
     }
+
+    #[test]
+    fn test_file_is_scanned_lazily() {
+        let csv = Fixture::setup("data.csv");
+
+        let reader = CsvReadOptions {
+            path: Some(csv.path),
+            columns: Some(Arc::new([PlSmallStr::from("Column2")])),
+            ..CsvReadOptions::default()
+        };
+
+        let df = reader.extract().unwrap();
+        let plan = df.clone().describe_optimized_plan().unwrap();
+        let result = df.collect().unwrap();
+
+        assert!(plan.contains("SCAN"), "{plan}");
+        assert!(!plan.contains("DF ["), "{plan}");
+        assert_eq!(result.get_column_names(), ["Column2"]);
+    }
+
+    #[test]
+    fn test_parse_options_are_applied() {
+        let csv = Fixture::blank("data.csv");
+        let mut file = File::create(csv.path.clone()).expect("Temp file creation failed");
+        file.write_all(b"# exported\na;b\n1;NA\n2;3\n").expect("Failed to write to temp file");
+
+        let reader = CsvReadOptions {
+            path: Some(csv.path),
+            n_rows: Some(1),
+            ..CsvReadOptions::default()
+        }.map_parse_options(|options| options
+            .with_separator(b';')
+            .with_comment_prefix(Some("#"))
+            .with_null_values(Some(NullValues::AllColumnsSingle(PlSmallStr::from("NA")))));
+
+        let result = reader.extract().unwrap().collect().unwrap();
+
+        assert_eq!(result.shape(), (1, 2));
+        assert_eq!(result.column("b").unwrap().null_count(), 1);
+    }
+
+    fn write_file(dir: &TempDir, filename: &str, content: &[u8]) -> PathBuf {
+        let path = dir.path().join(filename);
+        let mut file = File::create(&path).expect("Temp file creation failed");
+        file.write_all(content).expect("Failed to write to temp file");
+        path
+    }
+
+    #[test]
+    fn test_can_read_glob_diagonally_with_file_paths() {
+        let tempdir = tempfile::tempdir().unwrap();
+        write_file(&tempdir, "2024-01-01.csv", b"id,amount\n1,10\n2,20\n");
+        write_file(&tempdir, "2024-01-02.csv", b"id,amount,currency\n3,30.5,EUR\n");
+        write_file(&tempdir, "notes.txt", b"not a csv\n");
+
+        let reader = MultiCsvSource {
+            concat: Concat::DiagonalRelaxed,
+            include_file_paths: Some(String::from("source_file")),
+            ..MultiCsvSource::new(vec![tempdir.path().join("*.csv")])
+        };
+
+        let result = reader.extract().unwrap()
+            .sort(["id"], Default::default())
+            .collect()
+            .unwrap();
+        let files = result.column("source_file").unwrap().str().unwrap()
+            .into_no_null_iter()
+            .map(|path| PathBuf::from(path).file_name().unwrap().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(result.shape(), (3, 4));
+        assert_eq!(result.column("amount").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(result.column("currency").unwrap().null_count(), 2);
+        assert_eq!(files, ["2024-01-01.csv", "2024-01-01.csv", "2024-01-02.csv"]);
+    }
+
+    #[test]
+    fn test_can_read_list_of_paths() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let first = write_file(&tempdir, "a.csv", b"id,name\n1,Alice\n");
+        let second = write_file(&tempdir, "b.csv", b"id,name\n2,Bob\n");
+
+        let reader = MultiCsvSource {
+            options: CsvReadOptions {
+                columns: Some(Arc::new([PlSmallStr::from("name")])),
+                row_index: Some(RowIndex { name: PlSmallStr::from("row"), offset: 0 }),
+                ..CsvReadOptions::default()
+            },
+            ..MultiCsvSource::new(vec![first, second])
+        };
+
+        let result = reader.extract().unwrap().collect().unwrap();
+        let expected = df!(
+            "row" => &[0u32, 1],
+            "name" => &["Alice", "Bob"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_row_limit_applies_across_files() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let first = write_file(&tempdir, "a.csv", b"id\n1\n2\n");
+        let second = write_file(&tempdir, "b.csv", b"id\n3\n4\n");
+
+        let reader = MultiCsvSource {
+            options: CsvReadOptions {
+                n_rows: Some(3),
+                ..CsvReadOptions::default()
+            },
+            ..MultiCsvSource::new(vec![first, second])
+        };
+
+        let result = reader.extract().unwrap().collect().unwrap();
+
+        assert_eq!(result.column("id").unwrap().i64().unwrap().to_vec(), [Some(1), Some(2), Some(3)]);
+    }
 }
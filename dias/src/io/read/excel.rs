@@ -0,0 +1,255 @@
+use std::path::PathBuf;
+
+use calamine::{open_workbook_auto, Data, DataType as _, Range, Reader as _};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::reader::Reader;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Sheet {
+    Name(String),
+    Index(usize),
+}
+
+// Works for xlsx, xlsm, xlsb, xls and ods workbooks
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExcelSource {
+    pub path: PathBuf,
+    pub sheet: Sheet,
+    // Cell range in A1 notation, e.g. `B2:D20`, defaults to the used area of the sheet
+    pub range: Option<String>,
+    // Row holding the column names, relative to the range. Columns are named
+    // `column_0`, `column_1`, ... when there is none
+    pub header_row: Option<usize>,
+    // When disabled every column is read as text
+    pub infer_types: bool,
+}
+
+impl Default for ExcelSource {
+    fn default() -> ExcelSource {
+        ExcelSource {
+            path: PathBuf::new(),
+            sheet: Sheet::Index(0),
+            range: None,
+            header_row: Some(0),
+            infer_types: true
+        }
+    }
+}
+
+fn excel_err(err: impl std::fmt::Display) -> PolarsError {
+    polars_err!(ComputeError: "failed to read workbook: {}", err)
+}
+
+fn parse_cell(cell: &str) -> Option<(u32, u32)> {
+    let split = cell.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = cell.split_at(split);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    // The letters come from the configuration, a long run of them must not overflow
+    let column = letters
+        .to_ascii_uppercase()
+        .bytes()
+        .try_fold(0u32, |acc, c| acc.checked_mul(26)?.checked_add((c - b'A' + 1) as u32))?;
+    let row = digits.parse::<u32>().ok()?;
+    if row == 0 {
+        return None;
+    }
+    Some((row - 1, column - 1))
+}
+
+fn parse_range(range: &str) -> PolarsResult<((u32, u32), (u32, u32))> {
+    let bounds = range
+        .split_once(':')
+        .and_then(|(start, end)| Some((parse_cell(start.trim())?, parse_cell(end.trim())?)));
+    match bounds {
+        Some((start, end)) if start.0 <= end.0 && start.1 <= end.1 => Ok((start, end)),
+        _ => polars_bail!(InvalidOperation: "invalid cell range `{}`, expected A1 notation such as `A1:C10`", range)
+    }
+}
+
+fn is_integral(cell: &Data) -> bool {
+    match cell {
+        Data::Int(_) => true,
+        Data::Float(value) => value.fract() == 0.0 && value.abs() < i64::MAX as f64,
+        _ => false
+    }
+}
+
+fn is_missing(cell: &&Data) -> bool {
+    matches!(cell, Data::Empty | Data::Error(_))
+}
+
+fn text(cell: &Data) -> Option<String> {
+    match cell {
+        Data::Empty | Data::Error(_) => None,
+        Data::String(value) => Some(value.clone()),
+        other => Some(other.to_string())
+    }
+}
+
+fn infer_column(name: PlSmallStr, cells: &[&Data]) -> Series {
+    let mut values = cells.iter().filter(|cell| !is_missing(cell)).peekable();
+    if values.peek().is_none() {
+        return Series::new(name, cells.iter().map(|cell| text(cell)).collect::<Vec<_>>());
+    }
+
+    if values.clone().all(|cell| is_integral(cell)) {
+        return Series::new(name, cells.iter().map(|cell| cell.as_i64()).collect::<Vec<_>>());
+    }
+    if values.clone().all(|cell| matches!(cell, Data::Int(_) | Data::Float(_))) {
+        return Series::new(name, cells.iter().map(|cell| cell.as_f64()).collect::<Vec<_>>());
+    }
+    if values.clone().all(|cell| matches!(cell, Data::Bool(_))) {
+        return Series::new(name, cells.iter().map(|cell| cell.get_bool()).collect::<Vec<_>>());
+    }
+    if values.all(|cell| cell.as_datetime().is_some()) {
+        return Series::new(name, cells.iter().map(|cell| cell.as_datetime()).collect::<Vec<_>>());
+    }
+    Series::new(name, cells.iter().map(|cell| text(cell)).collect::<Vec<_>>())
+}
+
+impl ExcelSource {
+    fn read_range(&self) -> PolarsResult<Range<Data>> {
+        let mut workbook = open_workbook_auto(&self.path).map_err(excel_err)?;
+        let sheet = match &self.sheet {
+            Sheet::Name(name) => workbook.worksheet_range(name).map_err(excel_err)?,
+            Sheet::Index(index) => workbook
+                .worksheet_range_at(*index)
+                .ok_or_else(|| polars_err!(ComputeError: "workbook has no sheet at index {}", index))?
+                .map_err(excel_err)?
+        };
+        match &self.range {
+            Some(range) => {
+                let (start, end) = parse_range(range)?;
+                Ok(sheet.range(start, end))
+            },
+            None => Ok(sheet)
+        }
+    }
+
+    fn read(&self) -> PolarsResult<DataFrame> {
+        let range = self.read_range()?;
+        let rows = range.rows().collect::<Vec<_>>();
+        let width = range.width();
+
+        let (names, body) = match self.header_row {
+            Some(header_row) => {
+                let Some(header) = rows.get(header_row) else {
+                    polars_bail!(ComputeError: "header row {} is outside of the selected range", header_row);
+                };
+                let names = (0..width)
+                    .map(|index| match header.get(index).and_then(text) {
+                        Some(name) if !name.is_empty() => PlSmallStr::from(name),
+                        _ => PlSmallStr::from(format!("column_{index}"))
+                    })
+                    .collect::<Vec<_>>();
+                (names, &rows[header_row + 1..])
+            },
+            None => {
+                let names = (0..width)
+                    .map(|index| PlSmallStr::from(format!("column_{index}")))
+                    .collect::<Vec<_>>();
+                (names, &rows[..])
+            }
+        };
+
+        let columns = names
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let cells = body.iter().map(|row| &row[index]).collect::<Vec<_>>();
+                if self.infer_types {
+                    infer_column(name, &cells)
+                } else {
+                    Series::new(name, cells.iter().map(|cell| text(cell)).collect::<Vec<_>>())
+                }
+            })
+            .collect::<Vec<_>>();
+        DataFrame::new(columns)
+    }
+}
+
+impl Reader for ExcelSource {
+    fn extract(self) -> PolarsResult<LazyFrame> {
+        Ok(self.read()?.lazy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_xlsxwriter::Workbook;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, PathBuf) {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("report.xlsx");
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet().set_name("Summary").unwrap();
+        let sheet = workbook.add_worksheet().set_name("Orders").unwrap();
+        sheet.write(0, 0, "Exported by finance").unwrap();
+        sheet.write(2, 1, "customer").unwrap();
+        sheet.write(2, 2, "amount").unwrap();
+        sheet.write(2, 3, "paid").unwrap();
+        for (row, (customer, amount, paid)) in [("Alice", 100.0, true), ("Bob", 250.5, false)].into_iter().enumerate() {
+            sheet.write(row as u32 + 3, 1, customer).unwrap();
+            sheet.write(row as u32 + 3, 2, amount).unwrap();
+            sheet.write(row as u32 + 3, 3, paid).unwrap();
+        }
+        workbook.save(&path).unwrap();
+        (tempdir, path)
+    }
+
+    #[test]
+    fn test_can_parse_range() {
+        assert_eq!(parse_range("B2:AA10").unwrap(), ((1, 1), (9, 26)));
+        assert!(parse_range("B2").is_err());
+        assert!(parse_range("C3:A1").is_err());
+        assert!(parse_range("A1:ZZZZZZZ10").is_err());
+    }
+
+    #[test]
+    fn test_can_read_sheet_range_with_types() {
+        let (_tempdir, path) = setup();
+
+        let reader = ExcelSource {
+            path,
+            sheet: Sheet::Name(String::from("Orders")),
+            range: Some(String::from("B3:D5")),
+            ..Default::default()
+        };
+
+        let result = reader.extract().unwrap().collect().unwrap();
+        let expected = df!(
+            "customer" => &["Alice", "Bob"],
+            "amount" => &[100.0, 250.5],
+            "paid" => &[true, false]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_can_read_without_header_or_inference() {
+        let (_tempdir, path) = setup();
+
+        let reader = ExcelSource {
+            path,
+            sheet: Sheet::Index(1),
+            range: Some(String::from("C4:C5")),
+            header_row: None,
+            infer_types: false,
+        };
+
+        let result = reader.extract().unwrap().collect().unwrap();
+        let expected = df!(
+            "column_0" => &["100", "250.5"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,253 @@
+use std::path::PathBuf;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::transform::text::parse::ParseTextOp;
+use super::reader::Reader;
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Trim {
+    None,
+    #[default]
+    Both,
+    Start,
+    End,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FixedWidthColumn {
+    pub name: String,
+    // Zero based character offset of the field within the line
+    pub start: usize,
+    pub width: usize,
+    #[serde(default = "default_dtype")]
+    pub dtype: DataType,
+    #[serde(default)]
+    pub trim: Trim,
+    // Used for Date, Time and Datetime columns, the format is inferred when missing
+    #[serde(default)]
+    pub format: Option<StrptimeOptions>,
+}
+
+fn default_dtype() -> DataType {
+    DataType::String
+}
+
+impl FixedWidthColumn {
+    pub fn new(name: impl Into<String>, start: usize, width: usize) -> FixedWidthColumn {
+        FixedWidthColumn {
+            name: name.into(),
+            start,
+            width,
+            dtype: default_dtype(),
+            trim: Trim::default(),
+            format: None
+        }
+    }
+
+    pub fn with_dtype(mut self, dtype: DataType) -> FixedWidthColumn {
+        self.dtype = dtype;
+        self
+    }
+
+    fn slice_expr(&self) -> Expr {
+        let field = col(LINE).str().slice(lit(self.start as u64), lit(self.width as u64));
+        let field = match self.trim {
+            Trim::None => field,
+            Trim::Both => field.str().strip_chars(lit(Null {})),
+            Trim::Start => field.str().strip_chars_start(lit(Null {})),
+            Trim::End => field.str().strip_chars_end(lit(Null {}))
+        };
+        // Blank fields are missing values
+        when(field.clone().str().len_chars().eq(lit(0)))
+            .then(lit(Null {}).cast(DataType::String))
+            .otherwise(field)
+            .alias(&self.name)
+    }
+
+    fn convert_expr(&self) -> Option<Expr> {
+        let options = self.format.clone().unwrap_or_default();
+        let parse = match &self.dtype {
+            DataType::String => return None,
+            DataType::Date => ParseTextOp::ToDate {
+                column: self.name.clone(),
+                options,
+                alias: self.name.clone()
+            },
+            DataType::Time => ParseTextOp::ToTime {
+                column: self.name.clone(),
+                options,
+                alias: self.name.clone()
+            },
+            DataType::Datetime(time_unit, time_zone) => ParseTextOp::ToDateTime {
+                column: self.name.clone(),
+                options,
+                time_unit: Some(*time_unit),
+                time_zone: time_zone.clone(),
+                alias: self.name.clone()
+            },
+            dtype => return Some(col(&self.name).strict_cast(dtype.clone()))
+        };
+        Some(parse.to_expr())
+    }
+}
+
+const LINE: &str = "__dias_line";
+
+// Reads files whose fields sit at fixed character positions, as produced by mainframe
+// exports. Lines shorter than a field yield a missing value for it
+#[derive(Serialize, Deserialize)]
+pub struct FixedWidthSource {
+    pub path: PathBuf,
+    pub columns: Vec<FixedWidthColumn>,
+    // Lines skipped before the first record, e.g. a header banner
+    #[serde(default)]
+    pub skip_rows: usize,
+    #[serde(default)]
+    pub n_rows: Option<usize>,
+}
+
+impl FixedWidthSource {
+    pub fn new(path: impl Into<PathBuf>, columns: Vec<FixedWidthColumn>) -> FixedWidthSource {
+        FixedWidthSource {
+            path: path.into(),
+            columns,
+            skip_rows: 0,
+            n_rows: None
+        }
+    }
+
+    fn validate(&self) -> PolarsResult<()> {
+        polars_ensure!(!self.columns.is_empty(), InvalidOperation: "fixed width source needs at least one column");
+        let mut names = PlHashSet::new();
+        for column in &self.columns {
+            polars_ensure!(column.width > 0, InvalidOperation: "column `{}` has a width of zero", column.name);
+            polars_ensure!(names.insert(&column.name), Duplicate: "column `{}` is defined more than once", column.name);
+        }
+        Ok(())
+    }
+
+    fn read_lines(&self) -> PolarsResult<Series> {
+        let content = std::fs::read_to_string(&self.path)?;
+        let lines = content
+            .lines()
+            .skip(self.skip_rows)
+            .filter(|line| !line.trim().is_empty())
+            .take(self.n_rows.unwrap_or(usize::MAX));
+        Ok(Series::new(LINE.into(), lines.collect::<Vec<_>>()))
+    }
+}
+
+impl Reader for FixedWidthSource {
+    fn extract(self) -> PolarsResult<LazyFrame> {
+        self.validate()?;
+        let lines = DataFrame::new(vec![self.read_lines()?])?;
+        let fields = self.columns
+            .iter()
+            .map(|column| column.slice_expr())
+            .collect::<Vec<_>>();
+        let conversions = self.columns
+            .iter()
+            .filter_map(|column| column.convert_expr())
+            .collect::<Vec<_>>();
+
+        Ok(lines.lazy().select(fields).with_columns(conversions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::fs::File;
+    use std::io::prelude::*;
+    use tempfile::TempDir;
+
+    struct Fixture {
+        path: PathBuf,
+        _tempdir: TempDir,
+    }
+    impl Fixture {
+        fn setup() -> Self {
+            let tempdir = tempfile::tempdir().unwrap();
+            let path = tempdir.path().join("data.txt");
+            let mut file = File::create(&path).expect("Temp file creation failed");
+            file.write_all(concat!(
+                "ORDERS EXPORT\n",
+                "00001Alice     0000100.5020240102\n",
+                "00002Bob       0000200.00        \n",
+                "00003Charlie   0000300.2520240315\n"
+            ).as_bytes()).expect("Failed to write to temp file");
+
+            Fixture { _tempdir: tempdir, path }
+        }
+    }
+
+    fn make_columns() -> Vec<FixedWidthColumn> {
+        vec![
+            FixedWidthColumn::new("id", 0, 5).with_dtype(DataType::Int64),
+            FixedWidthColumn::new("name", 5, 10),
+            FixedWidthColumn::new("amount", 15, 10).with_dtype(DataType::Float64),
+            FixedWidthColumn {
+                format: Some(StrptimeOptions { format: Some("%Y%m%d".into()), ..Default::default() }),
+                ..FixedWidthColumn::new("ordered_on", 25, 8).with_dtype(DataType::Date)
+            },
+        ]
+    }
+
+    #[test]
+    fn test_can_read_typed_columns() {
+        let file = Fixture::setup();
+        let reader = FixedWidthSource {
+            skip_rows: 1,
+            ..FixedWidthSource::new(&file.path, make_columns())
+        };
+
+        let result = reader.extract().unwrap().collect().unwrap();
+        let expected = df!(
+            "id" => &[1i64, 2, 3],
+            "name" => &["Alice", "Bob", "Charlie"],
+            "amount" => &[100.5, 200.0, 300.25],
+            "ordered_on" => &[
+                NaiveDate::from_ymd_opt(2024, 1, 2),
+                None,
+                NaiveDate::from_ymd_opt(2024, 3, 15)
+            ]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_can_keep_padding() {
+        let file = Fixture::setup();
+        let reader = FixedWidthSource {
+            skip_rows: 1,
+            n_rows: Some(1),
+            ..FixedWidthSource::new(&file.path, vec![
+                FixedWidthColumn { trim: Trim::None, ..FixedWidthColumn::new("name", 5, 10) },
+                FixedWidthColumn { trim: Trim::Start, ..FixedWidthColumn::new("gap", 10, 8) },
+            ])
+        };
+
+        let result = reader.extract().unwrap().collect().unwrap();
+        let expected = df!(
+            "name" => &["Alice     "],
+            "gap" => &["000"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_duplicate_columns_are_rejected() {
+        let file = Fixture::setup();
+        let reader = FixedWidthSource::new(&file.path, vec![
+            FixedWidthColumn::new("name", 0, 5),
+            FixedWidthColumn::new("name", 5, 10),
+        ]);
+
+        assert!(reader.extract().is_err());
+    }
+}
@@ -0,0 +1,179 @@
+use std::fs::File;
+use std::path::PathBuf;
+
+use polars::io::{HiveOptions, RowIndex};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::reader::Reader;
+
+// Reads Arrow IPC files, Feather v2 included. `path` may be a single file, a glob or
+// a (hive partitioned) directory when scanning
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct IpcSource {
+    pub path: PathBuf,
+    pub columns: Option<Vec<String>>,
+    pub n_rows: Option<usize>,
+    pub row_index: Option<RowIndex>,
+    pub rechunk: bool,
+    pub hive_options: HiveOptions,
+    // Scans map uncompressed files instead of copying them. Disable it when the file may
+    // change while the pipeline runs, e.g. a sink overwriting its own source; the single
+    // file is then read into memory up front, so hive partitioning is not available.
+    // The polars scan has no switch for the memory map
+    pub memory_map: bool,
+}
+
+impl Default for IpcSource {
+    fn default() -> IpcSource {
+        let args = ScanArgsIpc::default();
+        IpcSource {
+            path: PathBuf::new(),
+            columns: None,
+            n_rows: args.n_rows,
+            row_index: args.row_index,
+            rechunk: args.rechunk,
+            hive_options: args.hive_options,
+            memory_map: true,
+        }
+    }
+}
+
+impl IpcSource {
+    pub fn new(path: impl Into<PathBuf>) -> IpcSource {
+        IpcSource {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+
+    fn scan(self) -> PolarsResult<LazyFrame> {
+        let args = ScanArgsIpc {
+            n_rows: self.n_rows,
+            row_index: self.row_index.clone(),
+            rechunk: self.rechunk,
+            hive_options: self.hive_options,
+            ..Default::default()
+        };
+        let df = LazyFrame::scan_ipc(&self.path, args)?;
+
+        match self.columns {
+            Some(columns) => {
+                let mut selection = Vec::new();
+                if let Some(row_index) = self.row_index {
+                    selection.push(col(row_index.name));
+                }
+                selection.extend(columns.iter().map(col));
+                Ok(df.select(selection))
+            },
+            None => Ok(df)
+        }
+    }
+
+    fn read(self) -> PolarsResult<DataFrame> {
+        let mut columns = self.columns;
+        if let (Some(columns), Some(row_index)) = (columns.as_mut(), &self.row_index) {
+            columns.insert(0, row_index.name.to_string());
+        }
+        let mut df = IpcReader::new(File::open(&self.path)?)
+            .with_n_rows(self.n_rows)
+            .with_row_index(self.row_index)
+            .with_columns(columns)
+            .finish()?;
+        if self.rechunk {
+            df.as_single_chunk_par();
+        }
+        Ok(df)
+    }
+}
+
+impl Reader for IpcSource {
+    fn extract(self) -> PolarsResult<LazyFrame> {
+        if self.memory_map {
+            return self.scan();
+        }
+        polars_ensure!(
+            self.hive_options == HiveOptions::default(),
+            InvalidOperation: "hive options need the memory mapped scan, they cannot be used with `memory_map` disabled"
+        );
+        Ok(self.read()?.lazy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct Fixture {
+        path: PathBuf,
+        _tempdir: TempDir,
+    }
+    impl Fixture {
+        fn setup() -> Self {
+            let tempdir = tempfile::tempdir().unwrap();
+            let path = tempdir.path().join("data.arrow");
+            let mut df = df!(
+                "customer_id" => &[1, 2, 3],
+                "name" => &["Alice", "Bob", "Charlie"],
+                "amount" => &[100, 200, 300]
+            ).unwrap();
+            IpcWriter::new(File::create(&path).unwrap()).finish(&mut df).unwrap();
+
+            Fixture { _tempdir: tempdir, path }
+        }
+    }
+
+    #[test]
+    fn test_can_scan_lazily() {
+        let ipc = Fixture::setup();
+        let reader = IpcSource {
+            columns: Some(vec![String::from("name"), String::from("amount")]),
+            row_index: Some(RowIndex { name: "row".into(), offset: 0 }),
+            ..IpcSource::new(&ipc.path)
+        };
+
+        let df = reader.extract().unwrap().filter(col("amount").gt(lit(100)));
+        let plan = df.explain(true).unwrap();
+        let result = df.collect().unwrap();
+        let expected = df!(
+            "row" => &[1u32, 2],
+            "name" => &["Bob", "Charlie"],
+            "amount" => &[200, 300]
+        ).unwrap();
+
+        assert!(plan.contains("Ipc SCAN"), "{plan}");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_can_read_without_memory_map() {
+        let ipc = Fixture::setup();
+        let reader = IpcSource {
+            columns: Some(vec![String::from("name")]),
+            n_rows: Some(2),
+            memory_map: false,
+            ..IpcSource::new(&ipc.path)
+        };
+
+        let result = reader.extract().unwrap().collect().unwrap();
+        let expected = df!(
+            "name" => &["Alice", "Bob"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_hive_options_need_memory_map() {
+        let ipc = Fixture::setup();
+        let reader = IpcSource {
+            memory_map: false,
+            hive_options: HiveOptions { enabled: Some(false), ..Default::default() },
+            ..IpcSource::new(&ipc.path)
+        };
+
+        assert!(matches!(reader.extract(), Err(PolarsError::InvalidOperation(_))));
+    }
+}
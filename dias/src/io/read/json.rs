@@ -0,0 +1,130 @@
+use std::fs::File;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::io::json_format::JsonFormatDef;
+use super::reader::Reader;
+
+// Nested objects are read as struct columns
+#[derive(Serialize, Deserialize)]
+pub struct JsonSource {
+    pub path: PathBuf,
+    #[serde(with = "JsonFormatDef")]
+    pub format: JsonFormat,
+    #[serde(default = "default_infer_schema_length")]
+    pub infer_schema_length: Option<NonZeroUsize>,
+    #[serde(default)]
+    pub schema: Option<SchemaRef>,
+    #[serde(default)]
+    pub schema_overwrite: Option<SchemaRef>,
+    #[serde(default)]
+    pub n_rows: Option<usize>,
+    #[serde(default)]
+    pub ignore_errors: bool,
+}
+
+fn default_infer_schema_length() -> Option<NonZeroUsize> {
+    NonZeroUsize::new(100)
+}
+
+impl JsonSource {
+    pub fn new(path: impl Into<PathBuf>, format: JsonFormat) -> JsonSource {
+        JsonSource {
+            path: path.into(),
+            format,
+            infer_schema_length: default_infer_schema_length(),
+            schema: None,
+            schema_overwrite: None,
+            n_rows: None,
+            ignore_errors: false
+        }
+    }
+
+    // JSON arrays have to be parsed as a whole, only NDJSON can be scanned lazily
+    fn read_array(self) -> PolarsResult<DataFrame> {
+        let mut reader = JsonReader::new(File::open(&self.path)?)
+            .with_json_format(JsonFormat::Json)
+            .infer_schema_len(self.infer_schema_length)
+            .with_ignore_errors(self.ignore_errors);
+        if let Some(schema) = self.schema {
+            reader = reader.with_schema(schema);
+        }
+        let df = match &self.schema_overwrite {
+            Some(schema_overwrite) => reader.with_schema_overwrite(schema_overwrite).finish()?,
+            None => reader.finish()?
+        };
+        Ok(df.head(self.n_rows))
+    }
+}
+
+impl Reader for JsonSource {
+    fn extract(self) -> PolarsResult<LazyFrame> {
+        match self.format {
+            JsonFormat::JsonLines => {
+                LazyJsonLineReader::new(&self.path)
+                    .with_infer_schema_length(self.infer_schema_length)
+                    .with_schema(self.schema)
+                    .with_schema_overwrite(self.schema_overwrite)
+                    .with_n_rows(self.n_rows)
+                    .with_ignore_errors(self.ignore_errors)
+                    .finish()
+            },
+            JsonFormat::Json => Ok(self.read_array()?.lazy())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::prelude::*;
+    use tempfile::TempDir;
+
+    fn setup(filename: &str, content: &[u8]) -> (TempDir, PathBuf) {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join(filename);
+        let mut file = File::create(&path).expect("Temp file creation failed");
+        file.write_all(content).expect("Failed to write to temp file");
+        (tempdir, path)
+    }
+
+    #[test]
+    fn test_can_scan_ndjson_with_nested_objects() {
+        let (_tempdir, path) = setup(
+            "events.jsonl",
+            b"{\"id\":1,\"user\":{\"name\":\"Alice\"}}\n{\"id\":2,\"user\":{\"name\":\"Bob\"}}\n"
+        );
+
+        let result = JsonSource::new(path, JsonFormat::JsonLines)
+            .extract().unwrap()
+            .select([col("user").struct_().field_by_name("name")])
+            .collect()
+            .unwrap();
+        let expected = df!(
+            "name" => &["Alice", "Bob"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_can_read_json_array_with_schema_overwrite() {
+        let (_tempdir, path) = setup(
+            "export.json",
+            b"[{\"id\":1,\"amount\":10},{\"id\":2,\"amount\":20},{\"id\":3,\"amount\":30}]"
+        );
+        let schema_overwrite = Schema::from_iter([Field::new("id".into(), DataType::String)]);
+
+        let result = JsonSource {
+            schema_overwrite: Some(Arc::new(schema_overwrite)),
+            n_rows: Some(2),
+            ..JsonSource::new(path, JsonFormat::Json)
+        }.extract().unwrap().collect().unwrap();
+
+        assert_eq!(result.shape(), (2, 2));
+        assert_eq!(result.column("id").unwrap().dtype(), &DataType::String);
+    }
+}
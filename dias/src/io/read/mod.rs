@@ -1,2 +1,9 @@
-mod reader;
+pub mod reader;
 pub mod csv;
+pub mod parquet;
+pub mod json;
+pub mod excel;
+pub mod fixed_width;
+pub mod ipc;
+pub mod sql;
+pub mod schema;
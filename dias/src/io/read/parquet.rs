@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+
+use polars::io::{HiveOptions, RowIndex};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::reader::Reader;
+
+// `path` may be a single file, a glob or a (hive partitioned) directory
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParquetSource {
+    pub path: PathBuf,
+    pub columns: Option<Vec<String>>,
+    pub n_rows: Option<usize>,
+    pub row_index: Option<RowIndex>,
+    pub parallel: ParallelStrategy,
+    pub use_statistics: bool,
+    pub low_memory: bool,
+    pub glob: bool,
+    pub hive_options: HiveOptions,
+}
+
+impl Default for ParquetSource {
+    fn default() -> ParquetSource {
+        let args = ScanArgsParquet::default();
+        ParquetSource {
+            path: PathBuf::new(),
+            columns: None,
+            n_rows: args.n_rows,
+            row_index: args.row_index,
+            parallel: args.parallel,
+            use_statistics: args.use_statistics,
+            low_memory: args.low_memory,
+            glob: args.glob,
+            hive_options: args.hive_options,
+        }
+    }
+}
+
+impl ParquetSource {
+    pub fn new(path: impl Into<PathBuf>) -> ParquetSource {
+        ParquetSource {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Reader for ParquetSource {
+    fn extract(self) -> PolarsResult<LazyFrame> {
+        let args = ScanArgsParquet {
+            n_rows: self.n_rows,
+            row_index: self.row_index.clone(),
+            parallel: self.parallel,
+            use_statistics: self.use_statistics,
+            low_memory: self.low_memory,
+            glob: self.glob,
+            hive_options: self.hive_options,
+            ..Default::default()
+        };
+        let df = LazyFrame::scan_parquet(&self.path, args)?;
+
+        match self.columns {
+            Some(columns) => {
+                let mut selection = Vec::new();
+                if let Some(row_index) = self.row_index {
+                    selection.push(col(row_index.name));
+                }
+                selection.extend(columns.iter().map(col));
+                Ok(df.select(selection))
+            },
+            None => Ok(df)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, File};
+    use tempfile::TempDir;
+
+    fn setup_partitions() -> TempDir {
+        let tempdir = tempfile::tempdir().unwrap();
+        for (date, amounts) in [("2024-01-01", [1, 2]), ("2024-01-02", [3, 4])] {
+            let partition = tempdir.path().join(format!("date={date}"));
+            fs::create_dir(&partition).unwrap();
+            let mut df = df!(
+                "amount" => &amounts,
+                "label" => &["a", "b"]
+            ).unwrap();
+            let file = File::create(partition.join("data.parquet")).unwrap();
+            ParquetWriter::new(file).finish(&mut df).unwrap();
+        }
+        tempdir
+    }
+
+    #[test]
+    fn test_can_scan_hive_partitions() {
+        let tempdir = setup_partitions();
+
+        let reader = ParquetSource {
+            columns: Some(vec![String::from("date"), String::from("amount")]),
+            ..ParquetSource::new(tempdir.path())
+        };
+
+        let result = reader.extract().unwrap()
+            .filter(col("amount").gt(lit(1)))
+            .sort(["amount"], Default::default())
+            .collect()
+            .unwrap();
+
+        assert_eq!(result.get_column_names(), ["date", "amount"]);
+        assert_eq!(result.column("date").unwrap().dtype(), &DataType::Date);
+        assert_eq!(result.column("amount").unwrap().i32().unwrap().to_vec(), [Some(2), Some(3), Some(4)]);
+    }
+
+    #[test]
+    fn test_can_scan_glob_with_row_limit() {
+        let tempdir = setup_partitions();
+
+        let reader = ParquetSource {
+            n_rows: Some(3),
+            hive_options: HiveOptions { enabled: Some(false), ..Default::default() },
+            ..ParquetSource::new(tempdir.path().join("*").join("*.parquet"))
+        };
+
+        let result = reader.extract().unwrap().collect().unwrap();
+
+        assert_eq!(result.shape(), (3, 2));
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::reader::Reader;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnInference {
+    pub name: String,
+    pub dtype: DataType,
+    // Share of the sampled values backing the inferred type, from 0 to 1. Missing values
+    // give no evidence and text columns lose confidence for every value that would also
+    // parse as a number or a date
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaReport {
+    pub schema: SchemaRef,
+    pub columns: Vec<ColumnInference>,
+}
+
+// Types a text column is checked against, strictest first
+const NARROWER_TYPES: [DataType; 3] = [DataType::Int64, DataType::Float64, DataType::Date];
+
+fn confidence(column: &Series) -> PolarsResult<f64> {
+    if column.is_empty() {
+        return Ok(0.0);
+    }
+    let values = column.drop_nulls();
+    let evidence = values.len() as f64 / column.len() as f64;
+    if values.is_empty() || column.dtype() != &DataType::String {
+        return Ok(evidence);
+    }
+
+    let mut ambiguous = 0;
+    for dtype in NARROWER_TYPES.iter() {
+        let parsed = values.cast(dtype)?;
+        ambiguous = ambiguous.max(parsed.len() - parsed.null_count());
+    }
+    Ok(evidence * (1.0 - ambiguous as f64 / values.len() as f64))
+}
+
+impl SchemaReport {
+    // Only the first `sample_rows` rows are collected
+    pub fn infer(df: LazyFrame, sample_rows: usize) -> PolarsResult<SchemaReport> {
+        let sample = df.limit(sample_rows as IdxSize).collect()?;
+        let columns = sample
+            .get_columns()
+            .iter()
+            .map(|column| Ok(ColumnInference {
+                name: column.name().to_string(),
+                dtype: column.dtype().clone(),
+                confidence: confidence(column)?
+            }))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        Ok(SchemaReport { schema: sample.schema().into(), columns })
+    }
+
+    pub fn from_reader(reader: impl Reader, sample_rows: usize) -> PolarsResult<SchemaReport> {
+        SchemaReport::infer(reader.extract()?, sample_rows)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ColumnContract {
+    pub name: String,
+    // Any type is accepted when missing
+    #[serde(default)]
+    pub dtype: Option<DataType>,
+    #[serde(default = "default_nullable")]
+    pub nullable: bool,
+}
+
+fn default_nullable() -> bool {
+    true
+}
+
+impl ColumnContract {
+    pub fn new(name: impl Into<String>, dtype: DataType) -> ColumnContract {
+        ColumnContract { name: name.into(), dtype: Some(dtype), nullable: true }
+    }
+
+    pub fn not_null(mut self) -> ColumnContract {
+        self.nullable = false;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    MissingColumn { column: String },
+    UnexpectedColumn { column: String },
+    Dtype { column: String, expected: DataType, found: DataType },
+    Nulls { column: String, null_count: usize },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::MissingColumn { column } => write!(f, "column `{column}` is missing"),
+            Violation::UnexpectedColumn { column } => write!(f, "column `{column}` is not part of the contract"),
+            Violation::Dtype { column, expected, found } => {
+                write!(f, "column `{column}` is {found}, expected {expected}")
+            },
+            Violation::Nulls { column, null_count } => {
+                write!(f, "column `{column}` is not nullable but holds {null_count} null values")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SchemaContract {
+    pub columns: Vec<ColumnContract>,
+    // Columns outside of the contract are violations when disabled
+    #[serde(default = "default_allow_extra_columns")]
+    pub allow_extra_columns: bool,
+}
+
+fn default_allow_extra_columns() -> bool {
+    true
+}
+
+impl SchemaContract {
+    pub fn new(columns: Vec<ColumnContract>) -> SchemaContract {
+        SchemaContract { columns, allow_extra_columns: default_allow_extra_columns() }
+    }
+
+    // Types are checked on the plan, nullability needs a pass over the non-nullable columns
+    pub fn violations(&self, df: &LazyFrame) -> PolarsResult<Vec<Violation>> {
+        let schema = df.clone().collect_schema()?;
+        let mut violations = Vec::new();
+        let mut not_null = Vec::new();
+
+        for column in &self.columns {
+            let Some(found) = schema.get(&column.name) else {
+                violations.push(Violation::MissingColumn { column: column.name.clone() });
+                continue;
+            };
+            match &column.dtype {
+                Some(expected) if expected != found => violations.push(Violation::Dtype {
+                    column: column.name.clone(),
+                    expected: expected.clone(),
+                    found: found.clone()
+                }),
+                _ => {}
+            }
+            if !column.nullable {
+                not_null.push(col(&column.name).null_count());
+            }
+        }
+        if !self.allow_extra_columns {
+            violations.extend(schema
+                .iter_names()
+                .filter(|name| !self.columns.iter().any(|column| column.name == name.as_str()))
+                .map(|name| Violation::UnexpectedColumn { column: name.to_string() }));
+        }
+
+        if !not_null.is_empty() {
+            let counts = df.clone().select(not_null).collect()?;
+            for column in counts.get_columns() {
+                let null_count = column.cast(&IDX_DTYPE)?.idx()?.get(0).unwrap_or_default() as usize;
+                if null_count > 0 {
+                    violations.push(Violation::Nulls { column: column.name().to_string(), null_count });
+                }
+            }
+        }
+        Ok(violations)
+    }
+
+    pub fn enforce(&self, df: LazyFrame) -> PolarsResult<LazyFrame> {
+        let violations = self.violations(&df)?;
+        if violations.is_empty() {
+            return Ok(df);
+        }
+        let details = violations
+            .iter()
+            .map(|violation| format!("  - {violation}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        polars_bail!(SchemaMismatch: "schema contract violated:\n{}", details)
+    }
+}
+
+pub struct Contracted<R: Reader> {
+    pub reader: R,
+    pub contract: SchemaContract,
+}
+
+impl<R: Reader> Reader for Contracted<R> {
+    fn extract(self) -> PolarsResult<LazyFrame> {
+        self.contract.enforce(self.reader.extract()?)
+    }
+}
+
+pub trait WithContract: Reader + Sized {
+    fn with_contract(self, contract: SchemaContract) -> Contracted<Self> {
+        Contracted { reader: self, contract }
+    }
+}
+
+impl<R: Reader> WithContract for R {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct InMemory(DataFrame);
+
+    impl Reader for InMemory {
+        fn extract(self) -> PolarsResult<LazyFrame> {
+            Ok(self.0.lazy())
+        }
+    }
+
+    fn make_df() -> DataFrame {
+        df!(
+            "customer_id" => &[1, 2, 3, 4],
+            "name" => &[Some("Alice"), Some("Bob"), None, Some("Dan")],
+            "amount" => &["100", "200", "3O0", "n/a"]
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_can_report_inference_confidence() {
+        let report = SchemaReport::from_reader(InMemory(make_df()), 100).unwrap();
+        let confidences = report.columns
+            .iter()
+            .map(|column| (column.name.as_str(), column.confidence))
+            .collect::<Vec<_>>();
+
+        assert_eq!(report.schema.get("amount"), Some(&DataType::String));
+        assert_eq!(confidences, [("customer_id", 1.0), ("name", 0.75), ("amount", 0.5)]);
+    }
+
+    #[test]
+    fn test_contract_lists_every_violation() {
+        let contract = SchemaContract {
+            allow_extra_columns: false,
+            ..SchemaContract::new(vec![
+                ColumnContract::new("name", DataType::String).not_null(),
+                ColumnContract::new("amount", DataType::Int64),
+                ColumnContract::new("country", DataType::String),
+            ])
+        };
+
+        let result = InMemory(make_df()).with_contract(contract).extract();
+        let message = result.err().unwrap().to_string();
+
+        assert!(message.contains("column `name` is not nullable but holds 1 null values"), "{message}");
+        assert!(message.contains("column `amount` is str, expected i64"), "{message}");
+        assert!(message.contains("column `country` is missing"), "{message}");
+        assert!(message.contains("column `customer_id` is not part of the contract"), "{message}");
+    }
+
+    #[test]
+    fn test_contract_passes_matching_frames() {
+        let contract = SchemaContract::new(vec![
+            ColumnContract::new("customer_id", DataType::Int32).not_null(),
+            ColumnContract { name: String::from("amount"), dtype: None, nullable: false },
+        ]);
+
+        let result = InMemory(make_df()).with_contract(contract).extract().unwrap().collect().unwrap();
+
+        assert_eq!(make_df(), result);
+    }
+}
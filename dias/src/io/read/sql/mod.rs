@@ -0,0 +1,294 @@
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::reader::Reader;
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+// One variant per driver, each gated behind the cargo feature that pulls its client
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Database {
+    // Path to the database file, opened read-only
+    #[cfg(feature = "sqlite")]
+    Sqlite(std::path::PathBuf),
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SqlSource {
+    pub database: Database,
+    pub query: String,
+    // Rows pulled from the cursor before they are turned into a chunk
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    // Takes precedence over the types declared by the database
+    #[serde(default)]
+    pub schema_overwrite: Option<SchemaRef>,
+    // Date and datetime columns travel as text, malformed values become nulls when disabled
+    #[serde(default = "default_strict_temporal")]
+    pub strict_temporal: bool,
+}
+
+fn default_batch_size() -> usize {
+    10_000
+}
+
+fn default_strict_temporal() -> bool {
+    true
+}
+
+impl SqlSource {
+    pub fn new(database: Database, query: impl Into<String>) -> SqlSource {
+        SqlSource {
+            database,
+            query: query.into(),
+            batch_size: default_batch_size(),
+            schema_overwrite: None,
+            strict_temporal: default_strict_temporal()
+        }
+    }
+}
+
+// Maps a declared column type to a polars type following SQLite's affinity rules, which
+// also cover the usual Postgres and MySQL spellings. Expressions and unknown types
+// yield None and are inferred from the fetched values instead
+pub fn sql_type_to_dtype(declared: &str) -> Option<DataType> {
+    let declared = declared.to_ascii_uppercase();
+    let name = declared.split('(').next().unwrap_or_default().trim();
+    let dtype = match name {
+        "BOOL" | "BOOLEAN" => DataType::Boolean,
+        "DATE" => DataType::Date,
+        "DATETIME" | "TIMESTAMP" | "TIMESTAMPTZ" => DataType::Datetime(TimeUnit::Microseconds, None),
+        _ if name.contains("INT") || name.contains("SERIAL") => DataType::Int64,
+        _ if ["CHAR", "CLOB", "TEXT", "UUID", "JSON"].iter().any(|t| name.contains(t)) => DataType::String,
+        _ if ["BLOB", "BYTEA", "BINARY"].iter().any(|t| name.contains(t)) => DataType::Binary,
+        _ if ["REAL", "FLOA", "DOUB", "NUMERIC", "DECIMAL"].iter().any(|t| name.contains(t)) => DataType::Float64,
+        _ => return None
+    };
+    Some(dtype)
+}
+
+// Collects rows handed over by a driver and turns them into a DataFrame every `batch_size`
+// rows, so the driver never has to hold more than one batch of native values
+#[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+pub(crate) struct Batches {
+    names: Vec<PlSmallStr>,
+    dtypes: Vec<Option<DataType>>,
+    columns: Vec<Vec<AnyValue<'static>>>,
+    batch_size: usize,
+    frames: Vec<DataFrame>,
+}
+
+#[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+impl Batches {
+    pub(crate) fn new(
+        columns: Vec<(String, Option<DataType>)>,
+        batch_size: usize,
+        schema_overwrite: Option<&Schema>
+    ) -> PolarsResult<Batches> {
+        polars_ensure!(batch_size > 0, InvalidOperation: "batch size must be greater than zero");
+        let (names, dtypes): (Vec<_>, Vec<_>) = columns
+            .into_iter()
+            .map(|(name, dtype)| {
+                let dtype = schema_overwrite
+                    .and_then(|schema| schema.get(&name).cloned())
+                    .or(dtype);
+                (PlSmallStr::from(name), dtype)
+            })
+            .unzip();
+        Ok(Batches {
+            columns: vec![Vec::with_capacity(batch_size); names.len()],
+            names,
+            dtypes,
+            batch_size,
+            frames: vec![]
+        })
+    }
+
+    pub(crate) fn push_row(&mut self, row: impl IntoIterator<Item = AnyValue<'static>>) -> PolarsResult<()> {
+        for (column, value) in self.columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+        if self.columns.first().is_some_and(|column| column.len() >= self.batch_size) {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> PolarsResult<()> {
+        let mut series = Vec::with_capacity(self.names.len());
+        for ((name, dtype), column) in self.names.iter().zip(self.dtypes.iter_mut()).zip(self.columns.iter_mut()) {
+            let values = std::mem::take(column);
+            let inferred = Series::from_any_values(name.clone(), &values, false)?;
+            match dtype {
+                // Dates travel as text and are parsed once all batches are stacked
+                Some(dtype) if dtype.is_temporal() => series.push(inferred.cast(&DataType::String)?),
+                Some(dtype) => series.push(inferred.cast(dtype)?),
+                // The first batch with values fixes the type of undeclared columns
+                None => {
+                    if !inferred.dtype().is_null() {
+                        *dtype = Some(inferred.dtype().clone());
+                    }
+                    series.push(inferred);
+                }
+            }
+        }
+        self.frames.push(DataFrame::new(series)?);
+        Ok(())
+    }
+
+    pub(crate) fn finish(mut self, strict_temporal: bool) -> PolarsResult<LazyFrame> {
+        if self.frames.is_empty() || self.columns.first().is_some_and(|column| !column.is_empty()) {
+            self.flush()?;
+        }
+        let frames = self.frames
+            .into_iter()
+            .map(|df| df.lazy())
+            .collect::<Vec<_>>();
+        let mut df = concat(frames, UnionArgs { to_supertypes: true, ..Default::default() })?.collect()?;
+
+        // Parsed one column at a time so a malformed value is reported against its column
+        let options = StrptimeOptions { strict: strict_temporal, ..Default::default() };
+        for (name, dtype) in self.names.iter().zip(&self.dtypes) {
+            let Some(dtype) = dtype else {
+                continue;
+            };
+            let parsed = match dtype {
+                DataType::Date => col(name.clone()).str().to_date(options.clone()),
+                DataType::Datetime(unit, zone) => col(name.clone()).str().to_datetime(
+                    Some(*unit),
+                    zone.clone(),
+                    options.clone(),
+                    lit("raise")
+                ),
+                _ => continue
+            };
+            df = df.lazy()
+                .with_column(parsed)
+                .collect()
+                .map_err(|err| polars_err!(
+                    ComputeError: "column `{}` is declared as {} but holds values that cannot be parsed: {}",
+                    name, dtype, err
+                ))?;
+        }
+        Ok(df.lazy())
+    }
+}
+
+impl Reader for SqlSource {
+    fn extract(self) -> PolarsResult<LazyFrame> {
+        match self.database {
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(path) => {
+                sqlite::read(&path, &self.query, self.batch_size, self.schema_overwrite.as_deref(), self.strict_temporal)
+            },
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    struct Fixture {
+        path: PathBuf,
+        _tempdir: TempDir,
+    }
+    impl Fixture {
+        fn setup() -> Self {
+            let tempdir = tempfile::tempdir().unwrap();
+            let path = tempdir.path().join("data.db");
+            let connection = rusqlite::Connection::open(&path).unwrap();
+            connection.execute_batch("
+                CREATE TABLE orders (
+                    id INTEGER PRIMARY KEY,
+                    name VARCHAR(20),
+                    amount REAL,
+                    paid BOOLEAN,
+                    ordered_on DATE,
+                    shipped_at TIMESTAMP
+                );
+                INSERT INTO orders VALUES
+                    (1, 'Alice', 100.5, 1, '2024-01-02', '2024-01-03 10:30:00'),
+                    (2, 'Bob', NULL, 0, '2024-02-10', NULL),
+                    (3, 'Charlie', 300.0, 1, '2024-03-15', '2024-03-16 08:00:00');
+            ").unwrap();
+
+            Fixture { _tempdir: tempdir, path }
+        }
+    }
+
+    #[test]
+    fn test_declared_types_are_mapped() {
+        let db = Fixture::setup();
+        let mut source = SqlSource::new(Database::Sqlite(db.path.clone()), "SELECT * FROM orders ORDER BY id");
+        source.batch_size = 2;
+
+        let result = source.extract().unwrap().collect().unwrap();
+        let schema = result.schema();
+
+        assert_eq!(result.height(), 3);
+        assert_eq!(schema.get("id"), Some(&DataType::Int64));
+        assert_eq!(schema.get("name"), Some(&DataType::String));
+        assert_eq!(schema.get("amount"), Some(&DataType::Float64));
+        assert_eq!(schema.get("paid"), Some(&DataType::Boolean));
+        assert_eq!(schema.get("ordered_on"), Some(&DataType::Date));
+        assert_eq!(schema.get("shipped_at"), Some(&DataType::Datetime(TimeUnit::Microseconds, None)));
+        assert_eq!(result.column("shipped_at").unwrap().null_count(), 1);
+        assert_eq!(
+            result.column("paid").unwrap().bool().unwrap().into_iter().collect::<Vec<_>>(),
+            vec![Some(true), Some(false), Some(true)]
+        );
+    }
+
+    #[test]
+    fn test_expressions_are_inferred() {
+        let db = Fixture::setup();
+        let mut source = SqlSource::new(
+            Database::Sqlite(db.path.clone()),
+            "SELECT paid, COUNT(*) AS orders, SUM(amount) AS total FROM orders GROUP BY paid ORDER BY paid"
+        );
+        source.schema_overwrite = Some(Arc::new(Schema::from_iter([Field::new("paid".into(), DataType::Int32)])));
+
+        let result = source.extract().unwrap().collect().unwrap();
+        let expected = df!(
+            "paid" => &[0i32, 1],
+            "orders" => &[1i64, 2],
+            "total" => &[None, Some(400.5)]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_empty_result_keeps_columns() {
+        let db = Fixture::setup();
+        let source = SqlSource::new(Database::Sqlite(db.path.clone()), "SELECT id, name FROM orders WHERE id > 10");
+
+        let result = source.extract().unwrap().collect().unwrap();
+
+        assert_eq!(result.height(), 0);
+        assert_eq!(result.schema().get("name"), Some(&DataType::String));
+    }
+
+    #[test]
+    fn test_malformed_dates_name_their_column() {
+        let db = Fixture::setup();
+        let connection = rusqlite::Connection::open(&db.path).unwrap();
+        connection.execute("INSERT INTO orders VALUES (4, 'Dan', 10.0, 0, '2024-13-45', NULL)", []).unwrap();
+
+        let strict = SqlSource::new(Database::Sqlite(db.path.clone()), "SELECT id, ordered_on FROM orders ORDER BY id");
+        let lenient = SqlSource {
+            strict_temporal: false,
+            ..SqlSource::new(Database::Sqlite(db.path.clone()), "SELECT id, ordered_on FROM orders ORDER BY id")
+        };
+
+        let message = strict.extract().err().unwrap().to_string();
+        let result = lenient.extract().unwrap().collect().unwrap();
+
+        assert!(message.contains("column `ordered_on` is declared as date"), "{message}");
+        assert_eq!(result.column("ordered_on").unwrap().null_count(), 1);
+    }
+}
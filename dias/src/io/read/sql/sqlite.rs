@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use polars::prelude::*;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+
+use super::{sql_type_to_dtype, Batches};
+
+fn sqlite_err(err: rusqlite::Error) -> PolarsError {
+    polars_err!(ComputeError: "sqlite query failed: {}", err)
+}
+
+fn to_any_value(value: ValueRef<'_>) -> AnyValue<'static> {
+    match value {
+        ValueRef::Null => AnyValue::Null,
+        ValueRef::Integer(value) => AnyValue::Int64(value),
+        ValueRef::Real(value) => AnyValue::Float64(value),
+        ValueRef::Text(value) => AnyValue::StringOwned(String::from_utf8_lossy(value).as_ref().into()),
+        ValueRef::Blob(value) => AnyValue::BinaryOwned(value.to_vec())
+    }
+}
+
+pub(super) fn read(
+    path: &Path,
+    query: &str,
+    batch_size: usize,
+    schema_overwrite: Option<&Schema>,
+    strict_temporal: bool
+) -> PolarsResult<LazyFrame> {
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(sqlite_err)?;
+    let mut statement = connection.prepare(query).map_err(sqlite_err)?;
+    let columns = statement
+        .columns()
+        .iter()
+        .map(|column| (column.name().to_string(), column.decl_type().and_then(sql_type_to_dtype)))
+        .collect::<Vec<_>>();
+    let width = columns.len();
+    let mut batches = Batches::new(columns, batch_size, schema_overwrite)?;
+
+    let mut rows = statement.query([]).map_err(sqlite_err)?;
+    while let Some(row) = rows.next().map_err(sqlite_err)? {
+        let values = (0..width)
+            .map(|index| row.get_ref(index).map(to_any_value))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sqlite_err)?;
+        batches.push_row(values)?;
+    }
+    batches.finish(strict_temporal)
+}
@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::writer::{check_appended_columns, has_content, open_file, WriteMode, Writer};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CsvSink {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub mode: WriteMode,
+    #[serde(default)]
+    pub options: CsvWriterOptions,
+}
+
+impl CsvSink {
+    pub fn new(path: impl Into<PathBuf>) -> CsvSink {
+        CsvSink {
+            path: path.into(),
+            mode: WriteMode::default(),
+            options: CsvWriterOptions::default()
+        }
+    }
+}
+
+impl Writer for CsvSink {
+    fn load(self, df: &mut DataFrame) -> PolarsResult<()> {
+        // The header and BOM are already at the start of a file being appended to
+        let appending = self.mode == WriteMode::Append && has_content(&self.path);
+        let serialize = self.options.serialize_options;
+        // Without a header there is nothing to compare the columns with
+        if appending && self.options.include_header {
+            let header = CsvReadOptions::default()
+                .with_n_rows(Some(0))
+                .map_parse_options(|options| options.with_separator(serialize.separator).with_quote_char(Some(serialize.quote_char)))
+                .try_into_reader_with_file_path(Some(self.path.clone()))?
+                .finish()?;
+            check_appended_columns(&self.path, &header, df)?;
+        }
+        let file = open_file(&self.path, self.mode)?;
+
+        CsvWriter::new(file)
+            .include_bom(self.options.include_bom && !appending)
+            .include_header(self.options.include_header && !appending)
+            .with_batch_size(self.options.batch_size)
+            .with_separator(serialize.separator)
+            .with_quote_char(serialize.quote_char)
+            .with_quote_style(serialize.quote_style)
+            .with_null_value(serialize.null)
+            .with_line_terminator(serialize.line_terminator)
+            .with_date_format(serialize.date_format)
+            .with_time_format(serialize.time_format)
+            .with_datetime_format(serialize.datetime_format)
+            .with_float_scientific(serialize.float_scientific)
+            .with_float_precision(serialize.float_precision)
+            .finish(df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn fixture() -> (TempDir, PathBuf) {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("output.csv");
+        (tempdir, path)
+    }
+
+    #[test]
+    fn test_can_write_with_separator() {
+        let (_tempdir, path) = fixture();
+        let mut df = df!(
+            "a" => &[1, 2],
+            "b" => &["x", "y"]
+        ).unwrap();
+        let mut sink = CsvSink::new(&path);
+        sink.options.serialize_options.separator = b';';
+
+        sink.load(&mut df).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a;b\n1;x\n2;y\n");
+    }
+
+    #[test]
+    fn test_append_skips_header() {
+        let (_tempdir, path) = fixture();
+        let mut df = df!(
+            "a" => &[1]
+        ).unwrap();
+
+        CsvSink::new(&path).load(&mut df).unwrap();
+        CsvSink { mode: WriteMode::Append, ..CsvSink::new(&path) }.load(&mut df).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a\n1\n1\n");
+    }
+
+    #[test]
+    fn test_append_checks_columns() {
+        let (_tempdir, path) = fixture();
+        let mut df = df!(
+            "a" => &[1],
+            "b" => &[2]
+        ).unwrap();
+        let mut reordered = df.select(["b", "a"]).unwrap();
+
+        CsvSink::new(&path).load(&mut df).unwrap();
+        let result = CsvSink { mode: WriteMode::Append, ..CsvSink::new(&path) }.load(&mut reordered);
+
+        assert!(matches!(result, Err(PolarsError::SchemaMismatch(_))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a,b\n1,2\n");
+    }
+
+    #[test]
+    fn test_error_if_exists() {
+        let (_tempdir, path) = fixture();
+        let mut df = df!(
+            "a" => &[1]
+        ).unwrap();
+
+        CsvSink::new(&path).load(&mut df).unwrap();
+        let result = CsvSink { mode: WriteMode::ErrorIfExists, ..CsvSink::new(&path) }.load(&mut df);
+
+        assert!(result.is_err());
+    }
+}
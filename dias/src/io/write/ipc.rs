@@ -0,0 +1,91 @@
+use std::fs::File;
+use std::path::PathBuf;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::writer::{open_file, rewrite_appended, WriteMode, Writer};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IpcSink {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub mode: WriteMode,
+    #[serde(default)]
+    pub options: IpcWriterOptions,
+}
+
+impl IpcSink {
+    pub fn new(path: impl Into<PathBuf>) -> IpcSink {
+        IpcSink {
+            path: path.into(),
+            mode: WriteMode::default(),
+            options: IpcWriterOptions::default()
+        }
+    }
+}
+
+impl Writer for IpcSink {
+    fn load(self, df: &mut DataFrame) -> PolarsResult<()> {
+        // IPC files end with a footer, appending rewrites the existing rows
+        if self.mode == WriteMode::Append && self.path.exists() {
+            let existing = IpcReader::new(File::open(&self.path)?).finish()?;
+            return rewrite_appended(&self.path, existing, df, |file, df| self.options.to_writer(file).finish(df));
+        }
+        let file = open_file(&self.path, self.mode)?;
+        self.options.to_writer(file).finish(df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_write_compressed_and_append() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("output.arrow");
+        let mut df = df!(
+            "a" => &[1, 2],
+            "b" => &["x", "y"]
+        ).unwrap();
+
+        IpcSink {
+            options: IpcWriterOptions {
+                compression: Some(IpcCompression::ZSTD),
+                ..Default::default()
+            },
+            ..IpcSink::new(&path)
+        }.load(&mut df).unwrap();
+        IpcSink { mode: WriteMode::Append, ..IpcSink::new(&path) }.load(&mut df).unwrap();
+
+        let result = IpcReader::new(File::open(&path).unwrap()).finish().unwrap();
+        let expected = df!(
+            "a" => &[1, 2, 1, 2],
+            "b" => &["x", "y", "x", "y"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_failed_append_keeps_original() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("output.arrow");
+        let mut df = df!(
+            "a" => &[1, 2],
+            "b" => &["x", "y"]
+        ).unwrap();
+        let mut other = df!(
+            "a" => &["1"],
+            "c" => &[true]
+        ).unwrap();
+
+        IpcSink::new(&path).load(&mut df).unwrap();
+        let result = IpcSink { mode: WriteMode::Append, ..IpcSink::new(&path) }.load(&mut other);
+
+        assert!(matches!(result, Err(PolarsError::SchemaMismatch(_))));
+        assert_eq!(df, IpcReader::new(File::open(&path).unwrap()).finish().unwrap());
+        assert_eq!(std::fs::read_dir(tempdir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::io::json_format::JsonFormatDef;
+use super::writer::{check_appended_columns, has_content, open_file, WriteMode, Writer};
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonSink {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub mode: WriteMode,
+    #[serde(with = "JsonFormatDef")]
+    pub format: JsonFormat,
+}
+
+impl JsonSink {
+    pub fn new(path: impl Into<PathBuf>, format: JsonFormat) -> JsonSink {
+        JsonSink {
+            path: path.into(),
+            mode: WriteMode::default(),
+            format
+        }
+    }
+}
+
+// `JsonFormat` does not implement Clone
+impl Clone for JsonSink {
+    fn clone(&self) -> Self {
+        let format = match &self.format {
+            JsonFormat::Json => JsonFormat::Json,
+            JsonFormat::JsonLines => JsonFormat::JsonLines
+        };
+        JsonSink { path: self.path.clone(), mode: self.mode, format }
+    }
+}
+
+impl Writer for JsonSink {
+    fn load(self, df: &mut DataFrame) -> PolarsResult<()> {
+        if self.mode == WriteMode::Append && has_content(&self.path) {
+            if matches!(self.format, JsonFormat::Json) {
+                polars_bail!(InvalidOperation: "cannot append to a JSON array, use JsonFormat::JsonLines instead");
+            }
+            let first = JsonLineReader::new(std::fs::File::open(&self.path)?)
+                .with_n_rows(Some(1))
+                .finish()?;
+            check_appended_columns(&self.path, &first, df)?;
+        }
+        let file = open_file(&self.path, self.mode)?;
+        JsonWriter::new(file)
+            .with_json_format(self.format)
+            .finish(df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_can_write_and_append_lines() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("output.jsonl");
+        let mut df = df!(
+            "a" => &[1],
+            "b" => &["x"]
+        ).unwrap();
+
+        JsonSink::new(&path, JsonFormat::JsonLines).load(&mut df).unwrap();
+        JsonSink { mode: WriteMode::Append, ..JsonSink::new(&path, JsonFormat::JsonLines) }.load(&mut df).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"a\":1,\"b\":\"x\"}\n{\"a\":1,\"b\":\"x\"}\n");
+    }
+
+    #[test]
+    fn test_append_checks_columns() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("output.jsonl");
+        let mut df = df!(
+            "a" => &[1]
+        ).unwrap();
+        let mut other = df!(
+            "a" => &[1],
+            "b" => &["x"]
+        ).unwrap();
+
+        JsonSink::new(&path, JsonFormat::JsonLines).load(&mut df).unwrap();
+        let result = JsonSink { mode: WriteMode::Append, ..JsonSink::new(&path, JsonFormat::JsonLines) }.load(&mut other);
+
+        assert!(matches!(result, Err(PolarsError::SchemaMismatch(_))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"a\":1}\n");
+    }
+
+    #[test]
+    fn test_cannot_append_to_json_array() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("output.json");
+        let mut df = df!(
+            "a" => &[1]
+        ).unwrap();
+
+        JsonSink::new(&path, JsonFormat::Json).load(&mut df).unwrap();
+        let result = JsonSink { mode: WriteMode::Append, ..JsonSink::new(&path, JsonFormat::Json) }.load(&mut df);
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[{\"a\":1}]");
+    }
+}
@@ -0,0 +1,5 @@
+pub mod writer;
+pub mod csv;
+pub mod parquet;
+pub mod json;
+pub mod ipc;
@@ -0,0 +1,101 @@
+use std::fs::File;
+use std::path::PathBuf;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::writer::{open_file, rewrite_appended, WriteMode, Writer};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ParquetSink {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub mode: WriteMode,
+    #[serde(default)]
+    pub options: ParquetWriteOptions,
+}
+
+impl ParquetSink {
+    pub fn new(path: impl Into<PathBuf>) -> ParquetSink {
+        ParquetSink {
+            path: path.into(),
+            mode: WriteMode::default(),
+            options: ParquetWriteOptions::default()
+        }
+    }
+
+    fn write(&self, file: File, df: &mut DataFrame) -> PolarsResult<()> {
+        ParquetWriter::new(file)
+            .with_compression(self.options.compression)
+            .with_statistics(self.options.statistics)
+            .with_row_group_size(self.options.row_group_size)
+            .with_data_page_size(self.options.data_page_size)
+            .finish(df)?;
+        Ok(())
+    }
+}
+
+impl Writer for ParquetSink {
+    fn load(self, df: &mut DataFrame) -> PolarsResult<()> {
+        // Parquet files cannot be extended in place, appending rewrites the existing rows
+        if self.mode == WriteMode::Append && self.path.exists() {
+            let existing = ParquetReader::new(File::open(&self.path)?).finish()?;
+            return rewrite_appended(&self.path, existing, df, |file, df| self.write(file, df));
+        }
+        let file = open_file(&self.path, self.mode)?;
+        self.write(file, df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_write_and_append() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("output.parquet");
+        let mut df = df!(
+            "a" => &[1, 2],
+            "b" => &["x", "y"]
+        ).unwrap();
+
+        ParquetSink {
+            options: ParquetWriteOptions {
+                compression: ParquetCompression::Snappy,
+                ..Default::default()
+            },
+            ..ParquetSink::new(&path)
+        }.load(&mut df).unwrap();
+        ParquetSink { mode: WriteMode::Append, ..ParquetSink::new(&path) }.load(&mut df).unwrap();
+
+        let result = ParquetReader::new(File::open(&path).unwrap()).finish().unwrap();
+        let expected = df!(
+            "a" => &[1, 2, 1, 2],
+            "b" => &["x", "y", "x", "y"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_failed_append_keeps_original() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("output.parquet");
+        let mut df = df!(
+            "a" => &[1, 2],
+            "b" => &["x", "y"]
+        ).unwrap();
+        let mut other = df!(
+            "a" => &["1"],
+            "c" => &[true]
+        ).unwrap();
+
+        ParquetSink::new(&path).load(&mut df).unwrap();
+        let result = ParquetSink { mode: WriteMode::Append, ..ParquetSink::new(&path) }.load(&mut other);
+
+        assert!(matches!(result, Err(PolarsError::SchemaMismatch(_))));
+        assert_eq!(df, ParquetReader::new(File::open(&path).unwrap()).finish().unwrap());
+        assert_eq!(std::fs::read_dir(tempdir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,66 @@
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub trait Writer {
+    fn load(self, df: &mut DataFrame) -> PolarsResult<()>;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WriteMode {
+    #[default]
+    Overwrite,
+    Append,
+    ErrorIfExists,
+}
+
+pub(crate) fn open_file(path: &Path, mode: WriteMode) -> PolarsResult<File> {
+    let file = match mode {
+        WriteMode::Overwrite => File::create(path),
+        WriteMode::Append => OpenOptions::new().create(true).append(true).open(path),
+        WriteMode::ErrorIfExists => OpenOptions::new().write(true).create_new(true).open(path),
+    };
+    Ok(file?)
+}
+
+// Appending to Parquet and IPC rewrites the whole file, the rows are checked against the
+// existing schema first and written to a sibling file so a failure leaves the original intact
+pub(crate) fn rewrite_appended(
+    path: &Path,
+    mut existing: DataFrame,
+    df: &DataFrame,
+    write: impl FnOnce(File, &mut DataFrame) -> PolarsResult<()>
+) -> PolarsResult<()> {
+    polars_ensure!(
+        existing.schema() == df.schema(),
+        SchemaMismatch: "cannot append to {}: expected schema {:?}, found {:?}", path.display(), existing.schema(), df.schema()
+    );
+    existing.vstack_mut(df)?;
+
+    let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let temporary = path.with_file_name(format!(".{file_name}.tmp"));
+    let written = File::create(&temporary)
+        .map_err(PolarsError::from)
+        .and_then(|file| write(file, &mut existing));
+    if let Err(err) = written {
+        let _ = std::fs::remove_file(&temporary);
+        return Err(err);
+    }
+    std::fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+// Text formats are appended in place, so the new rows must carry the columns already in the file
+pub(crate) fn check_appended_columns(path: &Path, existing: &DataFrame, df: &DataFrame) -> PolarsResult<()> {
+    polars_ensure!(
+        existing.get_column_names() == df.get_column_names(),
+        SchemaMismatch: "cannot append to {}: expected columns {:?}, found {:?}", path.display(), existing.get_column_names(), df.get_column_names()
+    );
+    Ok(())
+}
+
+pub(crate) fn has_content(path: &Path) -> bool {
+    path.metadata().map(|meta| meta.len() > 0).unwrap_or(false)
+}
@@ -1,2 +1,4 @@
-mod io;
-mod transform;
+pub mod error;
+pub mod io;
+pub mod transform;
+pub mod pipeline;
@@ -0,0 +1,426 @@
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::error::{DiasError, DiasResult};
+use crate::io::read::csv::MultiCsvSource;
+use crate::io::read::excel::ExcelSource;
+use crate::io::read::fixed_width::FixedWidthSource;
+use crate::io::read::ipc::IpcSource;
+use crate::io::read::json::JsonSource;
+use crate::io::read::parquet::ParquetSource;
+use crate::io::read::reader::Reader;
+use crate::io::read::schema::{SchemaContract, WithContract};
+use crate::io::read::sql::SqlSource;
+use crate::io::write::csv::CsvSink;
+use crate::io::write::ipc::IpcSink;
+use crate::io::write::json::JsonSink;
+use crate::io::write::parquet::ParquetSink;
+use crate::io::write::writer::Writer;
+use crate::transform::cast_cols::CastCols;
+use crate::transform::count_rows::CountRows;
+use crate::transform::exclude_cols::ExcludeCols;
+use crate::transform::filter::Filter;
+use crate::transform::group_by::group_by::GroupBy;
+use crate::transform::group_by::window::GroupByWindow;
+use crate::transform::join::join::Join;
+use crate::transform::rename_cols::RenameCols;
+use crate::transform::reorder_cols::ReorderCols;
+use crate::transform::reverse_rows::ReverseRows;
+use crate::transform::select_cols::SelectCols;
+use crate::transform::sort::Sort;
+use crate::transform::text::parse::ParseText;
+use crate::transform::transformer::Transformation;
+use crate::transform::transpose::Transpose;
+use crate::transform::validate::Validate;
+use crate::transform::with_columns::WithColumns;
+
+use super::Pipeline;
+
+// Bump whenever a change to the steps breaks previously saved definitions, and teach
+// `upgrade` how to bring the older format forward
+pub const FORMAT_VERSION: u32 = 2;
+// Oldest format that can still be upgraded to the current one
+pub const OLDEST_FORMAT_VERSION: u32 = 1;
+
+// Steps and sources are externally tagged, internal tagging buffers values and cannot
+// carry the i128 literals found in polars expressions
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum StepDefinition {
+    SelectCols(SelectCols),
+    ExcludeCols(ExcludeCols),
+    CountRows(CountRows),
+    ReverseRows(ReverseRows),
+    Sort(Sort),
+    Filter(Filter),
+    CastCols(CastCols),
+    Join(Join),
+    GroupBy(GroupBy),
+    GroupByWindow(GroupByWindow),
+    Transpose(Transpose),
+    ParseText(ParseText),
+    Validate(Validate),
+    WithColumns(WithColumns),
+    RenameCols(RenameCols),
+    ReorderCols(ReorderCols),
+}
+
+impl StepDefinition {
+    fn as_transformation(&self) -> &dyn Transformation {
+        match self {
+            StepDefinition::SelectCols(step) => step,
+            StepDefinition::ExcludeCols(step) => step,
+            StepDefinition::CountRows(step) => step,
+            StepDefinition::ReverseRows(step) => step,
+            StepDefinition::Sort(step) => step,
+            StepDefinition::Filter(step) => step,
+            StepDefinition::CastCols(step) => step,
+            StepDefinition::Join(step) => step,
+            StepDefinition::GroupBy(step) => step,
+            StepDefinition::GroupByWindow(step) => step,
+            StepDefinition::Transpose(step) => step,
+            StepDefinition::ParseText(step) => step,
+            StepDefinition::Validate(step) => step,
+            StepDefinition::WithColumns(step) => step,
+            StepDefinition::RenameCols(step) => step,
+            StepDefinition::ReorderCols(step) => step,
+        }
+    }
+}
+
+impl Transformation for StepDefinition {
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame> {
+        self.as_transformation().apply(df)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum SourceDefinition {
+    Csv(CsvReadOptions),
+    MultiCsv(MultiCsvSource),
+    Parquet(ParquetSource),
+    Json(JsonSource),
+    Excel(ExcelSource),
+    Ipc(IpcSource),
+    FixedWidth(FixedWidthSource),
+    Sql(SqlSource),
+}
+
+impl Reader for SourceDefinition {
+    fn extract(self) -> PolarsResult<LazyFrame> {
+        match self {
+            SourceDefinition::Csv(options) => options.extract(),
+            SourceDefinition::MultiCsv(source) => source.extract(),
+            SourceDefinition::Parquet(source) => source.extract(),
+            SourceDefinition::Json(source) => source.extract(),
+            SourceDefinition::Excel(source) => source.extract(),
+            SourceDefinition::Ipc(source) => source.extract(),
+            SourceDefinition::FixedWidth(source) => source.extract(),
+            SourceDefinition::Sql(source) => source.extract(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SinkDefinition {
+    Csv(CsvSink),
+    Parquet(ParquetSink),
+    Json(JsonSink),
+    Ipc(IpcSink),
+}
+
+impl Writer for SinkDefinition {
+    fn load(self, df: &mut DataFrame) -> PolarsResult<()> {
+        match self {
+            SinkDefinition::Csv(sink) => sink.load(df),
+            SinkDefinition::Parquet(sink) => sink.load(df),
+            SinkDefinition::Json(sink) => sink.load(df),
+            SinkDefinition::Ipc(sink) => sink.load(df),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PipelineDefinition {
+    pub version: u32,
+    pub source: SourceDefinition,
+    // Checked against the source before any step runs
+    #[serde(default)]
+    pub contract: Option<SchemaContract>,
+    #[serde(default)]
+    pub steps: Vec<StepDefinition>,
+    #[serde(default)]
+    pub sink: Option<SinkDefinition>,
+}
+
+#[derive(Deserialize)]
+struct Versioned {
+    version: u32,
+}
+
+fn check_version(version: u32) -> DiasResult<()> {
+    if !(OLDEST_FORMAT_VERSION..=FORMAT_VERSION).contains(&version) {
+        return Err(DiasError::UnsupportedVersion {
+            found: version,
+            oldest: OLDEST_FORMAT_VERSION,
+            supported: FORMAT_VERSION
+        });
+    }
+    Ok(())
+}
+
+// Version 1 filters were a main clause folded from left to right with And/Or branches
+fn upgrade_filter_v1(filter: &mut Value) {
+    let Some(filter) = filter.as_object_mut() else {
+        return;
+    };
+    let Some(main_clause) = filter.remove("main_clause") else {
+        return;
+    };
+    let branches = match filter.remove("branches") {
+        Some(Value::Array(branches)) => branches,
+        _ => vec![]
+    };
+    let mut condition = json!({ "Clause": main_clause });
+    for branch in branches {
+        let Value::Object(branch) = branch else {
+            continue;
+        };
+        for (operator, clause) in branch {
+            let mut group = Map::new();
+            group.insert(operator, json!([condition, { "Clause": clause }]));
+            condition = Value::Object(group);
+        }
+    }
+    filter.insert(String::from("condition"), condition);
+}
+
+// Brings an older definition to the current format, one version at a time
+fn upgrade(mut value: Value) -> DiasResult<Value> {
+    let Versioned { version } = Versioned::deserialize(&value)?;
+    check_version(version)?;
+    if version < 2 {
+        let steps = value.get_mut("steps").and_then(Value::as_array_mut).into_iter().flatten();
+        for filter in steps.filter_map(|step| step.get_mut("Filter")) {
+            upgrade_filter_v1(filter);
+        }
+    }
+    value["version"] = Value::from(FORMAT_VERSION);
+    Ok(value)
+}
+
+impl PipelineDefinition {
+    pub fn new(source: SourceDefinition, steps: Vec<StepDefinition>) -> PipelineDefinition {
+        PipelineDefinition {
+            version: FORMAT_VERSION,
+            source,
+            contract: None,
+            steps,
+            sink: None
+        }
+    }
+
+    pub fn from_json(json: &str) -> DiasResult<PipelineDefinition> {
+        let Versioned { version } = serde_json::from_str(json)?;
+        check_version(version)?;
+        if version == FORMAT_VERSION {
+            return Ok(serde_json::from_str(json)?);
+        }
+        PipelineDefinition::from_value(serde_json::from_str(json)?)
+    }
+
+    fn from_value(value: Value) -> DiasResult<PipelineDefinition> {
+        Ok(serde_json::from_value(upgrade(value)?)?)
+    }
+
+    pub fn to_json(&self) -> DiasResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    // serde_yaml cannot represent nested enums such as polars expressions directly,
+    // so YAML documents go through the same tree as JSON ones
+    pub fn from_yaml(yaml: &str) -> DiasResult<PipelineDefinition> {
+        PipelineDefinition::from_value(serde_yaml::from_str::<Value>(yaml)?)
+    }
+
+    pub fn to_yaml(&self) -> DiasResult<String> {
+        Ok(serde_yaml::to_string(&serde_json::to_value(self)?)?)
+    }
+
+    pub fn into_pipeline(self) -> Pipeline {
+        let mut pipeline = match self.contract {
+            Some(contract) => Pipeline::new(self.source.with_contract(contract)),
+            None => Pipeline::new(self.source)
+        };
+        for step in self.steps {
+            pipeline.push_step(Box::new(step));
+        }
+        match self.sink {
+            Some(sink) => pipeline.with_sink(sink),
+            None => pipeline
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::prelude::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    use crate::transform::filter::clause::{Clause, FilterOperators};
+    use crate::transform::selector::Selector;
+
+    struct Fixture {
+        path: PathBuf,
+        _tempdir: TempDir,
+    }
+    impl Fixture {
+        fn setup() -> Self {
+            let tempdir = tempfile::tempdir().unwrap();
+            let path = tempdir.path().join("data.csv");
+            let mut csv = File::create(&path).expect("Temp file creation failed");
+            csv.write_all(b"name,amount\nAlice,100\nBob,300\nCharlie,200\n").expect("Failed to write to temp file");
+
+            Fixture { _tempdir: tempdir, path }
+        }
+    }
+
+    fn make_definition(path: PathBuf) -> PipelineDefinition {
+        PipelineDefinition::new(
+            SourceDefinition::Csv(CsvReadOptions {
+                path: Some(path),
+                ..Default::default()
+            }),
+            vec![
+                StepDefinition::Filter(Filter::new(Clause {
+                    column: String::from("amount"),
+                    operator: FilterOperators::PolarsOperator(Operator::Gt, lit(150)),
+                })),
+                StepDefinition::Sort(Sort {
+                    by: vec![String::from("amount")],
+                    ..Default::default()
+                }),
+                StepDefinition::SelectCols(SelectCols::new(Selector::names(["name"]))),
+            ]
+        )
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let csv = Fixture::setup();
+        let json = make_definition(csv.path.clone()).to_json().unwrap();
+
+        let result = PipelineDefinition::from_json(&json).unwrap()
+            .into_pipeline()
+            .run()
+            .unwrap();
+        let expected = df!(
+            "name" => &["Charlie", "Bob"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let csv = Fixture::setup();
+        let yaml = make_definition(csv.path.clone()).to_yaml().unwrap();
+
+        let definition = PipelineDefinition::from_yaml(&yaml).unwrap();
+        assert_eq!(definition.version, FORMAT_VERSION);
+        assert!(matches!(definition.steps[1], StepDefinition::Sort(_)));
+
+        let result = definition.into_pipeline().run().unwrap();
+        let expected = df!(
+            "name" => &["Charlie", "Bob"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_sink_is_restored() {
+        let csv = Fixture::setup();
+        let output = csv.path.with_file_name("output.csv");
+        let mut definition = make_definition(csv.path.clone());
+        definition.sink = Some(SinkDefinition::Csv(CsvSink::new(&output)));
+        let json = definition.to_json().unwrap();
+
+        PipelineDefinition::from_json(&json).unwrap()
+            .into_pipeline()
+            .run()
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(output).unwrap(), "name\nCharlie\nBob\n");
+    }
+
+    #[test]
+    fn test_contract_is_enforced() {
+        let csv = Fixture::setup();
+        let json = r#"{"columns": [{"name": "amount", "dtype": "String"}]}"#;
+        let mut definition = make_definition(csv.path.clone());
+        definition.contract = Some(serde_json::from_str(json).unwrap());
+        let json = definition.to_json().unwrap();
+
+        let result = PipelineDefinition::from_json(&json).unwrap()
+            .into_pipeline()
+            .run();
+
+        assert!(matches!(result, Err(DiasError::Polars(PolarsError::SchemaMismatch(_)))));
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let csv = Fixture::setup();
+        let mut definition = make_definition(csv.path.clone());
+        definition.version = FORMAT_VERSION + 1;
+        let json = definition.to_json().unwrap();
+
+        let result = PipelineDefinition::from_json(&json);
+
+        assert!(matches!(result, Err(DiasError::UnsupportedVersion { .. })));
+    }
+
+    #[test]
+    fn test_version_one_filters_are_upgraded() {
+        let csv = Fixture::setup();
+        let mut definition = serde_json::to_value(make_definition(csv.path.clone())).unwrap();
+        let clause = |column: &str, op: Operator, value: Expr| serde_json::to_value(Clause {
+            column: String::from(column),
+            operator: FilterOperators::PolarsOperator(op, value)
+        }).unwrap();
+        definition["version"] = json!(1);
+        definition["steps"][0] = json!({
+            "Filter": {
+                "main_clause": clause("amount", Operator::Gt, lit(150)),
+                "branches": [{ "Or": clause("name", Operator::Eq, lit("Alice")) }]
+            }
+        });
+
+        let definition = PipelineDefinition::from_json(&definition.to_string()).unwrap();
+        assert_eq!(definition.version, FORMAT_VERSION);
+
+        let result = definition.into_pipeline().run().unwrap();
+        let expected = df!(
+            "name" => &["Alice", "Charlie", "Bob"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_unknown_old_version_is_rejected() {
+        let csv = Fixture::setup();
+        let mut definition = make_definition(csv.path.clone());
+        definition.version = OLDEST_FORMAT_VERSION - 1;
+        let json = definition.to_json().unwrap();
+
+        let result = PipelineDefinition::from_json(&json);
+
+        assert!(matches!(result, Err(DiasError::UnsupportedVersion { found: 0, .. })));
+    }
+}
@@ -0,0 +1,166 @@
+use polars::prelude::*;
+
+use crate::error::DiasResult;
+use crate::io::read::reader::Reader;
+use crate::io::write::writer::Writer;
+use crate::transform::transformer::Transformation;
+
+pub mod definition;
+
+type Source = Box<dyn FnOnce() -> PolarsResult<LazyFrame>>;
+type Sink = Box<dyn FnOnce(&mut DataFrame) -> PolarsResult<()>>;
+
+pub struct Pipeline {
+    source: Source,
+    steps: Vec<Box<dyn Transformation>>,
+    sink: Option<Sink>,
+}
+
+impl Pipeline {
+    pub fn new<R: Reader + 'static>(reader: R) -> Pipeline {
+        Pipeline {
+            source: Box::new(move || reader.extract()),
+            steps: Vec::new(),
+            sink: None,
+        }
+    }
+
+    pub fn with_step<T: Transformation + 'static>(mut self, step: T) -> Pipeline {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    pub fn push_step(&mut self, step: Box<dyn Transformation>) {
+        self.steps.push(step);
+    }
+
+    pub fn with_sink<W: Writer + 'static>(mut self, writer: W) -> Pipeline {
+        self.sink = Some(Box::new(move |df| writer.load(df)));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    // Builds the query plan without executing it, the sink is not called
+    pub fn run_lazy(self) -> DiasResult<LazyFrame> {
+        let df = (self.source)()?;
+        self.steps.iter().try_fold(df, |df, step| step.apply(df))
+    }
+
+    pub fn run(mut self) -> DiasResult<DataFrame> {
+        let sink = self.sink.take();
+        let mut df = self.run_lazy()?.collect()?;
+        if let Some(sink) = sink {
+            sink(&mut df)?;
+        }
+        Ok(df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::transform::count_rows::CountRows;
+    use crate::transform::reverse_rows::ReverseRows;
+    use crate::transform::select_cols::SelectCols;
+    use crate::transform::selector::Selector;
+    use crate::transform::sort::Sort;
+
+    struct InMemory(DataFrame);
+
+    struct Received(Rc<RefCell<usize>>);
+
+    impl Writer for Received {
+        fn load(self, df: &mut DataFrame) -> PolarsResult<()> {
+            *self.0.borrow_mut() = df.height();
+            Ok(())
+        }
+    }
+
+    impl Reader for InMemory {
+        fn extract(self) -> PolarsResult<LazyFrame> {
+            Ok(self.0.lazy())
+        }
+    }
+
+    fn source() -> InMemory {
+        InMemory(df!(
+            "a" => &[3, 1, 2],
+            "b" => &["c", "a", "b"]
+        ).unwrap())
+    }
+
+    #[test]
+    fn test_steps_applied_in_order() {
+        let pipeline = Pipeline::new(source())
+            .with_step(Sort {
+                by: vec![String::from("a")],
+                ..Default::default()
+            })
+            .with_step(ReverseRows {});
+
+        assert_eq!(pipeline.len(), 2);
+        let result = pipeline.run().unwrap();
+        let expected = df!(
+            "a" => &[3, 2, 1],
+            "b" => &["c", "b", "a"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_steps_built_at_runtime() {
+        let steps: Vec<Box<dyn Transformation>> = vec![
+            Box::new(Sort {
+                by: vec![String::from("b")],
+                descending: Some(true),
+                ..Default::default()
+            }),
+            Box::new(SelectCols::new(Selector::names(["a"]))),
+        ];
+        let mut pipeline = Pipeline::new(source());
+        for step in steps {
+            pipeline.push_step(step);
+        }
+
+        let result = pipeline.run().unwrap();
+        let expected = df!(
+            "a" => &[3, 2, 1]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_run_lazy_returns_plan() {
+        let result = Pipeline::new(source())
+            .with_step(CountRows {})
+            .run_lazy()
+            .unwrap()
+            .collect()
+            .unwrap();
+
+        assert_eq!(result.column("Row Count").unwrap().u32().unwrap().get(0), Some(3));
+    }
+
+    #[test]
+    fn test_sink_receives_result() {
+        let received = Rc::new(RefCell::new(0));
+        let result = Pipeline::new(source())
+            .with_sink(Received(received.clone()))
+            .run()
+            .unwrap();
+
+        assert_eq!(*received.borrow(), result.height());
+    }
+}
@@ -1,15 +1,22 @@
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::error::DiasResult;
 use super::transformer::Transformation;
 
-struct CastCols<'a> {
-    dtypes: PlHashMap<&'a str, DataType>,
+#[derive(Serialize, Deserialize)]
+pub struct CastCols {
+    dtypes: PlHashMap<String, DataType>,
     strict: bool
 }
 
-impl<'a> Transformation for CastCols<'a> {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
-        df.cast(self.dtypes, self.strict)
+impl Transformation for CastCols {
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame> {
+        let dtypes = self.dtypes
+            .iter()
+            .map(|(column, dtype)| (column.as_str(), dtype.clone()))
+            .collect::<PlHashMap<_, _>>();
+        Ok(df.cast(dtypes, self.strict))
     }
 }
 
@@ -21,8 +28,8 @@ mod tests {
     #[test]
     fn test_columns_can_be_cast() {
         let mut dtypes = PlHashMap::new();
-        dtypes.insert("a", DataType::Int8);
-        dtypes.insert("b", DataType::Int16);
+        dtypes.insert(String::from("a"), DataType::Int8);
+        dtypes.insert(String::from("b"), DataType::Int16);
         let transformation = CastCols {
             strict: false,
             dtypes
@@ -32,7 +39,7 @@ mod tests {
             "b" => [6,7,8,9,10],
             "c" => ["a", "b", "c", "d", "e"]
         }.unwrap();
-        let result = transformation.apply(df.lazy()).collect().unwrap();
+        let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
         
         assert_eq!(result.dtypes(), &[DataType::Int8, DataType::Int16, DataType::String]);
     }
@@ -1,12 +1,15 @@
 use polars::prelude::{len, LazyFrame};
+use serde::{Deserialize, Serialize};
 
+use crate::error::DiasResult;
 use super::transformer::Transformation;
 
+#[derive(Serialize, Deserialize)]
 pub struct CountRows {}
 
 impl Transformation for CountRows {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
-        df.select([len().alias("Row Count")])
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame> {
+        Ok(df.select([len().alias("Row Count")]))
     }
 }
 
@@ -23,7 +26,7 @@ mod tests {
             "col1" => &[1, 2, 3, 4, 5],
         ).ok().unwrap().lazy();
 
-        let result = transformation.apply(df.lazy()).collect().unwrap();
+        let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
 
         
         assert_eq!(result.column("Row Count").unwrap().sum::<i32>().unwrap(), 5);
@@ -1,14 +1,20 @@
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::error::DiasResult;
+use super::selector::Selector;
 use super::transformer::Transformation;
 
+#[derive(Serialize, Deserialize)]
 pub struct ExcludeCols {
-    columns: Vec<String>
+    columns: Selector
 }
 
 impl Transformation for ExcludeCols {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
-        df.select(&[col("*").exclude(self.columns)])
+    fn apply(&self, mut df: LazyFrame) -> DiasResult<LazyFrame> {
+        // Excluding a column that is not there leaves nothing to do
+        let columns = self.columns.resolve_present(&*df.collect_schema()?, "ExcludeCols")?;
+        Ok(df.select([col("*").exclude(columns)]))
     }
 }
 
@@ -16,12 +22,11 @@ impl Transformation for ExcludeCols {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::string::String;
 
     #[test]
     fn test_can_select_cols_subset(){
         let transformation = ExcludeCols {
-            columns: vec![String::from("col2"), String::from("col4")]
+            columns: Selector::names(["col2", "col4"])
         };
 
         let df = df!(
@@ -31,8 +36,24 @@ mod tests {
             "col4" => &[4],
         ).ok().unwrap().lazy();
 
-        let result = transformation.apply(df).collect().unwrap().schema();
+        let result = transformation.apply(df).unwrap().collect().unwrap().schema();
 
         assert_eq!(result.len(), 2);
     }
+
+    #[test]
+    fn test_absent_columns_are_ignored() {
+        let transformation = ExcludeCols {
+            columns: Selector::names(["col2", "missing"])
+        };
+
+        let df = df!(
+            "col1" => &[1],
+            "col2" => &[2],
+        ).ok().unwrap().lazy();
+
+        let result = transformation.apply(df).unwrap().collect().unwrap().schema();
+
+        assert_eq!(result.iter_names().map(|name| name.as_str()).collect::<Vec<_>>(), ["col1"]);
+    }
 }
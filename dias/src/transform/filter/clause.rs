@@ -1,25 +1,36 @@
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::error::{DiasError, DiasResult};
+
+#[derive(Serialize, Deserialize)]
 pub struct Clause {
     pub column: String,
     pub operator: FilterOperators,
 }
 
-impl Clause {
+fn compare(expr: Expr, op: Operator, value: Expr) -> DiasResult<Expr> {
+    if !op.is_comparison() {
+        return Err(DiasError::invalid_parameter("Filter", "operator", format!("expected a comparison operator, found `{op}`")));
+    }
+    Ok(binary_expr(expr, op, value))
+}
 
-    fn make_operation(&self, expr: Expr) -> Expr {
-        match &self.operator {
+impl FilterOperators {
+
+    fn apply(&self, expr: Expr) -> DiasResult<Expr> {
+        let expr = match self {
             FilterOperators::PolarsOperator(op, value) => {
                 if op.is_arithmetic() {
-                    panic!("Cannot use arithmetic operators in a filter step");
+                    return Err(DiasError::invalid_parameter("Filter", "operator", "cannot use arithmetic operators in a filter step"));
                 }
                 binary_expr(expr, *op, value.clone())
             },
             FilterOperators::PolarsBooleanFunction(boolean_func) => {
                 match boolean_func {
-                    BooleanFunction::Not => panic!("Cannot use `Not` Expression by itself to construct a Filter step"),
-                    BooleanFunction::AllHorizontal | BooleanFunction::AnyHorizontal => panic!("Cannot use AllHorizontal & AnyHorizontal to construct a Filter step"),
-                    BooleanFunction::IsIn => panic!("Cannot use BooleanFunction::IsIn, please use FilterOperators::IsIn instead"),
+                    BooleanFunction::Not => return Err(DiasError::invalid_parameter("Filter", "operator", "cannot use `Not` expression by itself to construct a Filter step, wrap the clause in Condition::Not instead")),
+                    BooleanFunction::AllHorizontal | BooleanFunction::AnyHorizontal => return Err(DiasError::invalid_parameter("Filter", "operator", "cannot use AllHorizontal & AnyHorizontal to construct a Filter step")),
+                    BooleanFunction::IsIn => return Err(DiasError::invalid_parameter("Filter", "operator", "cannot use BooleanFunction::IsIn, please use FilterOperators::IsIn instead")),
                     BooleanFunction::Any { ignore_nulls } => expr.any(*ignore_nulls),
                     BooleanFunction::All { ignore_nulls } => expr.all(*ignore_nulls),
                     BooleanFunction::IsNan => expr.is_nan(),
@@ -32,32 +43,73 @@ impl Clause {
             },
             FilterOperators::Contains { pattern, literal, strict } => {
                 if *literal {
-                    return expr.str().contains_literal(lit(pattern.clone()));
+                    expr.str().contains_literal(lit(pattern.clone()))
+                } else {
+                    expr.str().contains(lit(pattern.clone()), *strict)
                 }
-                expr.str().contains(lit(pattern.clone()), *strict)
             },
             FilterOperators::NotContains { pattern, literal, strict } => {
                 if *literal {
-                    return expr.str().contains_literal(lit(pattern.clone())).not();
+                    expr.str().contains_literal(lit(pattern.clone())).not()
+                } else {
+                    expr.str().contains(lit(pattern.clone()), *strict).not()
                 }
-                expr.str().contains(lit(pattern.clone()), *strict).not()
             },
             FilterOperators::StartsWith(substr) => expr.str().starts_with(lit(substr.clone())),
             FilterOperators::EndsWith(substr) => expr.str().ends_with(lit(substr.clone())),
             FilterOperators::NotStartsWith(substr) => expr.str().starts_with(lit(substr.clone())).not(),
             FilterOperators::NotEndsWith(substr) => expr.str().ends_with(lit(substr.clone())).not(),
 
-            FilterOperators::IsIn(e) => expr.is_in(e.clone())
-        }
+            FilterOperators::ContainsIgnoreCase { pattern, literal } => {
+                if *literal {
+                    expr.str().to_lowercase().str().contains_literal(lit(pattern.to_lowercase()))
+                } else {
+                    expr.str().contains(lit(format!("(?i){pattern}")), true)
+                }
+            },
+            FilterOperators::EqualsIgnoreCase(value) => expr.str().to_lowercase().eq(lit(value.to_lowercase())),
+            FilterOperators::Length(op, value) => compare(length(expr), *op, value.clone())?,
+
+            FilterOperators::Between { low, high, inclusive } => {
+                if *inclusive {
+                    expr.clone().gt_eq(low.clone()).and(expr.lt_eq(high.clone()))
+                } else {
+                    expr.clone().gt(low.clone()).and(expr.lt(high.clone()))
+                }
+            },
+
+            FilterOperators::IsIn(e) => expr.is_in(e.clone()),
+            FilterOperators::NotIn(e) => expr.is_in(e.clone()).not(),
+
+            FilterOperators::DatePart { part, operator, value } => compare(date_part(expr, *part), *operator, value.clone())?
+        };
+        Ok(expr)
     }
 
-    pub fn make_expr(&self) -> Expr {
+}
+
+impl Clause {
+    pub fn make_expr(&self) -> DiasResult<Expr> {
         let expr = col(&self.column);
 
-        self.make_operation(expr)
+        self.operator.apply(expr)
     }
 }
 
+// Clause over a computed left side, e.g. `col("price") * col("qty")`
+#[derive(Serialize, Deserialize)]
+pub struct ExprClause {
+    pub expr: Expr,
+    pub operator: FilterOperators,
+}
+
+impl ExprClause {
+    pub fn make_expr(&self) -> DiasResult<Expr> {
+        self.operator.apply(self.expr.clone())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
 pub enum FilterOperators{
     PolarsOperator(Operator, Expr),
     PolarsBooleanFunction(BooleanFunction),
@@ -68,6 +120,144 @@ pub enum FilterOperators{
     EndsWith(String),
     NotStartsWith(String),
     NotEndsWith(String),
+    // Regex patterns are matched with the `i` flag
+    ContainsIgnoreCase { pattern: String, literal: bool },
+    EqualsIgnoreCase(String),
+    // Compares the number of characters
+    Length(Operator, Expr),
+    // Range Filters
+    Between { low: Expr, high: Expr, inclusive: bool },
     // Lookup Filters
-    IsIn(Expr)
+    IsIn(Expr),
+    NotIn(Expr),
+    // Date Filters
+    DatePart { part: DatePart, operator: Operator, value: Expr },
+}
+
+// Weekdays go from 1 (Monday) to 7 (Sunday)
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum DatePart {
+    Year,
+    Month,
+    Day,
+    Weekday,
+}
+
+// Shared by filters, the query language and computed columns so they agree on semantics
+pub(crate) fn date_part(expr: Expr, part: DatePart) -> Expr {
+    match part {
+        DatePart::Year => expr.dt().year(),
+        DatePart::Month => expr.dt().month(),
+        DatePart::Day => expr.dt().day(),
+        DatePart::Weekday => expr.dt().weekday()
+    }
+}
+
+// Number of characters, not bytes
+pub(crate) fn length(expr: Expr) -> Expr {
+    expr.str().len_chars()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn make_df() -> DataFrame {
+        df!(
+            "name" => &["Alice", "bob", "BOBBY", "Charlie"],
+            "amount" => &[100, 200, 300, 400],
+            "ordered_on" => &[
+                NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 2, 3).unwrap(),
+                NaiveDate::from_ymd_opt(2023, 2, 4).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 3, 5).unwrap(),
+            ]
+        ).unwrap()
+    }
+
+    fn filter_names(column: &str, operator: FilterOperators) -> Vec<String> {
+        let clause = Clause { column: String::from(column), operator };
+        make_df().lazy()
+            .filter(clause.make_expr().unwrap())
+            .collect()
+            .unwrap()
+            .column("name")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_between() {
+        let inclusive = filter_names("amount", FilterOperators::Between { low: lit(200), high: lit(300), inclusive: true });
+        let exclusive = filter_names("amount", FilterOperators::Between { low: lit(100), high: lit(300), inclusive: false });
+
+        assert_eq!(inclusive, ["bob", "BOBBY"]);
+        assert_eq!(exclusive, ["bob"]);
+    }
+
+    #[test]
+    fn test_case_insensitive_strings() {
+        let literal = filter_names("name", FilterOperators::ContainsIgnoreCase { pattern: String::from("Bo"), literal: true });
+        let regex = filter_names("name", FilterOperators::ContainsIgnoreCase { pattern: String::from("^b.b$"), literal: false });
+        let equals = filter_names("name", FilterOperators::EqualsIgnoreCase(String::from("ALICE")));
+
+        assert_eq!(literal, ["bob", "BOBBY"]);
+        assert_eq!(regex, ["bob"]);
+        assert_eq!(equals, ["Alice"]);
+    }
+
+    #[test]
+    fn test_not_in_and_length() {
+        let not_in = filter_names("amount", FilterOperators::NotIn(lit(Series::new("".into(), [100, 400]))));
+        let length = filter_names("name", FilterOperators::Length(Operator::GtEq, lit(5)));
+
+        assert_eq!(not_in, ["bob", "BOBBY"]);
+        assert_eq!(length, ["Alice", "BOBBY", "Charlie"]);
+    }
+
+    #[test]
+    fn test_date_parts() {
+        let year = filter_names("ordered_on", FilterOperators::DatePart { part: DatePart::Year, operator: Operator::Eq, value: lit(2024) });
+        let month = filter_names("ordered_on", FilterOperators::DatePart { part: DatePart::Month, operator: Operator::Gt, value: lit(1) });
+        // 2024-02-03 is a Saturday
+        let weekday = filter_names("ordered_on", FilterOperators::DatePart { part: DatePart::Weekday, operator: Operator::Eq, value: lit(6) });
+
+        assert_eq!(year, ["Alice", "bob", "Charlie"]);
+        assert_eq!(month, ["bob", "BOBBY", "Charlie"]);
+        assert_eq!(weekday, ["bob", "BOBBY"]);
+    }
+
+    #[test]
+    fn test_expression_clause() {
+        let clause = ExprClause {
+            expr: col("amount") * lit(2) - col("name").str().len_chars(),
+            operator: FilterOperators::PolarsOperator(Operator::Gt, col("amount") + lit(100))
+        };
+
+        let result = make_df().lazy()
+            .filter(clause.make_expr().unwrap())
+            .select([col("amount")])
+            .collect()
+            .unwrap();
+
+        assert_eq!(result.column("amount").unwrap().i32().unwrap().to_vec(), [Some(200), Some(300), Some(400)]);
+    }
+
+    #[test]
+    fn test_date_part_needs_comparison() {
+        let clause = Clause {
+            column: String::from("ordered_on"),
+            operator: FilterOperators::DatePart { part: DatePart::Year, operator: Operator::Plus, value: lit(1) }
+        };
+
+        assert!(matches!(
+            clause.make_expr(),
+            Err(DiasError::InvalidParameter { step: "Filter", parameter: "operator", .. })
+        ));
+    }
 }
@@ -0,0 +1,73 @@
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DiasError, DiasResult};
+use super::clause::{Clause, ExprClause};
+
+// Boolean tree over clauses, groups are evaluated before their parent
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum Condition {
+    Clause(Clause),
+    ExprClause(ExprClause),
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    pub fn and(self, other: Condition) -> Condition {
+        match self {
+            Condition::And(mut conditions) => {
+                conditions.push(other);
+                Condition::And(conditions)
+            },
+            condition => Condition::And(vec![condition, other])
+        }
+    }
+
+    pub fn or(self, other: Condition) -> Condition {
+        match self {
+            Condition::Or(mut conditions) => {
+                conditions.push(other);
+                Condition::Or(conditions)
+            },
+            condition => Condition::Or(vec![condition, other])
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Condition {
+        Condition::Not(Box::new(self))
+    }
+
+    fn make_group(conditions: &[Condition], combine: fn(Expr, Expr) -> Expr) -> DiasResult<Expr> {
+        let mut exprs = conditions.iter().map(Condition::make_expr);
+        let first = exprs.next().ok_or_else(|| {
+            DiasError::invalid_parameter("Filter", "condition", "cannot build an empty And/Or group")
+        })??;
+        exprs.try_fold(first, |expr, other| Ok(combine(expr, other?)))
+    }
+
+    pub fn make_expr(&self) -> DiasResult<Expr> {
+        match self {
+            Condition::Clause(clause) => clause.make_expr(),
+            Condition::ExprClause(clause) => clause.make_expr(),
+            Condition::And(conditions) => Condition::make_group(conditions, Expr::and),
+            Condition::Or(conditions) => Condition::make_group(conditions, Expr::or),
+            Condition::Not(condition) => Ok(condition.make_expr()?.not())
+        }
+    }
+}
+
+impl From<Clause> for Condition {
+    fn from(clause: Clause) -> Condition {
+        Condition::Clause(clause)
+    }
+}
+
+impl From<ExprClause> for Condition {
+    fn from(clause: ExprClause) -> Condition {
+        Condition::ExprClause(clause)
+    }
+}
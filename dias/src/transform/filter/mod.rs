@@ -1,40 +1,45 @@
-mod clause;
+pub mod clause;
+pub mod condition;
+pub mod query;
 
-use clause::Clause;
+use condition::Condition;
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::error::DiasResult;
 use super::transformer::Transformation;
 
-enum Branch {
-    And(Clause),
-    Or(Clause),
-}
-fn make_branch(expr: Expr, branch: &Branch) -> Expr{
-    match branch {
-        Branch::And(clause) => expr.and(clause.make_expr()),
-        Branch::Or(clause) => expr.or(clause.make_expr()),
-    }
-}
+#[derive(Serialize, Deserialize)]
 pub struct Filter {
-    main_clause: Clause,
-    branches: Vec<Branch>
+    pub condition: Condition
 }
 
 impl Filter {
-    fn make_filter(self) -> Expr {
-        let filter = self.main_clause.make_expr();
-        self.branches.iter().fold(filter, make_branch)
+    pub fn new(condition: impl Into<Condition>) -> Filter {
+        Filter { condition: condition.into() }
+    }
+
+    pub fn parse(query: &str) -> DiasResult<Filter> {
+        Ok(Filter::new(query::parse(query)?))
     }
 }
 
 impl Transformation for Filter {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
-        df.filter(self.make_filter())
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame> {
+        Ok(df.filter(self.condition.make_expr()?))
     }
 }
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clause::{Clause, FilterOperators};
+    use crate::error::DiasError;
+
+    fn clause(column: &str, op: Operator, value: Expr) -> Condition {
+        Condition::Clause(Clause {
+            column: String::from(column),
+            operator: FilterOperators::PolarsOperator(op, value),
+        })
+    }
 
     #[test]
     fn test_can_filter_rows() {
@@ -43,19 +48,10 @@ mod tests {
            "b" => [Some(5), None, Some(2), Some(3), Some(1)]
        }.unwrap();
 
-       let transformation = Filter {
-           main_clause: Clause {
-               column: String::from("a"),
-               operator: clause::FilterOperators::PolarsOperator(Operator::GtEq, lit(3)),
-           },
-           branches: vec![
-               Branch::And(Clause {
-                   column: String::from("b"),
-                   operator: clause::FilterOperators::PolarsOperator(Operator::Lt, col("a")),
-               })
-           ]
-       };
-       let result = transformation.apply(df.lazy()).collect().unwrap();
+       let transformation = Filter::new(
+           clause("a", Operator::GtEq, lit(3)).and(clause("b", Operator::Lt, col("a")))
+       );
+       let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
        let expected = df! {
            "a" => [Some(3), Some(4)],
            "b" => [Some(2), Some(3)]
@@ -63,4 +59,78 @@ mod tests {
 
        assert_eq!(expected, result);
     }
+
+    #[test]
+    fn test_arithmetic_operator_is_rejected() {
+        let df = df! {
+           "a" => [1, 2, 3]
+        }.unwrap();
+
+        let transformation = Filter::new(clause("a", Operator::Plus, lit(1)));
+        let result = transformation.apply(df.lazy());
+
+        assert!(matches!(
+            result,
+            Err(DiasError::InvalidParameter { step: "Filter", parameter: "operator", .. })
+        ));
+    }
+
+    #[test]
+    fn test_can_group_conditions() {
+        let df = df! {
+           "a" => [1, 2, 3, 4, 5],
+           "b" => ["x", "y", "x", "y", "z"]
+        }.unwrap();
+
+        // a >= 2 AND (b == "x" OR a == 5)
+        let transformation = Filter::new(Condition::And(vec![
+            clause("a", Operator::GtEq, lit(2)),
+            Condition::Or(vec![
+                clause("b", Operator::Eq, lit("x")),
+                clause("a", Operator::Eq, lit(5)),
+            ]),
+        ]));
+        let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
+        let expected = df! {
+           "a" => [3, 5],
+           "b" => ["x", "z"]
+        }.unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_can_negate_group() {
+        let df = df! {
+           "a" => [1, 2, 3, 4, 5],
+           "b" => ["x", "y", "x", "y", "z"]
+        }.unwrap();
+
+        // NOT (b == "y" OR a > 4)
+        let transformation = Filter::new(
+            clause("b", Operator::Eq, lit("y")).or(clause("a", Operator::Gt, lit(4))).not()
+        );
+        let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
+        let expected = df! {
+           "a" => [1, 3],
+           "b" => ["x", "x"]
+        }.unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_empty_group_is_rejected() {
+        let df = df! {
+           "a" => [1, 2, 3]
+        }.unwrap();
+
+        let transformation = Filter::new(Condition::Or(vec![]));
+        let result = transformation.apply(df.lazy());
+
+        assert!(matches!(
+            result,
+            Err(DiasError::InvalidParameter { step: "Filter", parameter: "condition", .. })
+        ));
+    }
 }
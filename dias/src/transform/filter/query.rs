@@ -0,0 +1,611 @@
+// Parser for filter queries such as
+// `amount >= 100 and (name starts_with "Bo" or country in ["FR", "MA"])`
+//
+// query      := or
+// or         := and ("or" and)*
+// and        := unary ("and" unary)*
+// unary      := "not" unary | "(" query ")" | comparison
+// comparison := operand comparator operand | operand "is" ["not"] "null"
+//             | operand string_operator string | operand ("in" | "not_in" | "not" "in") list
+//             | operand "between" operand "and" operand
+// operand    := arithmetic over columns, literals and `len`, `year`, `month`, `day`
+//               or `weekday` calls, e.g. `price * qty` or `len(name) + 1`
+// column     := identifier | `quoted identifier`
+// list       := "[" [literal ("," literal)*] "]"
+use polars::prelude::*;
+
+use crate::error::{DiasError, DiasResult};
+use super::clause::{date_part, length, Clause, DatePart, ExprClause, FilterOperators};
+use super::condition::Condition;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Quoted(String),
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Symbol(&'static str),
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Ident(ident) => format!("`{ident}`"),
+            Token::Quoted(ident) => format!("`{ident}`"),
+            Token::Str(value) => format!("\"{value}\""),
+            Token::Int(value) => value.to_string(),
+            Token::Float(value) => value.to_string(),
+            Token::Symbol(symbol) => format!("`{symbol}`"),
+            Token::End => String::from("end of query")
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Ident(ident) if ident.eq_ignore_ascii_case(keyword))
+    }
+}
+
+fn error(position: usize, reason: impl Into<String>) -> DiasError {
+    DiasError::Query { position, reason: reason.into() }
+}
+
+// Longest symbols first so `<=` is not read as `<`
+const SYMBOLS: [&str; 18] = ["==", "!=", "<>", "<=", ">=", "=", "<", ">", "(", ")", "[", "]", ",", "+", "-", "*", "/", "%"];
+
+fn tokenize(query: &str) -> DiasResult<Vec<(Token, usize)>> {
+    let chars = query.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+
+    while position < chars.len() {
+        let start = position;
+        let c = chars[position];
+        if c.is_whitespace() {
+            position += 1;
+            continue;
+        }
+
+        let token = if c == '"' || c == '\'' || c == '`' {
+            let mut value = String::new();
+            position += 1;
+            loop {
+                match chars.get(position) {
+                    None => return Err(error(start, format!("unterminated {c} quote"))),
+                    Some('\\') if c != '`' => {
+                        let Some(escaped) = chars.get(position + 1) else {
+                            return Err(error(position, "dangling escape at end of query"));
+                        };
+                        value.push(*escaped);
+                        position += 2;
+                    },
+                    Some(next) if *next == c => {
+                        position += 1;
+                        break;
+                    },
+                    Some(next) => {
+                        value.push(*next);
+                        position += 1;
+                    }
+                }
+            }
+            if c == '`' { Token::Quoted(value) } else { Token::Str(value) }
+        } else if c.is_ascii_digit() {
+            position += 1;
+            while chars.get(position).is_some_and(|next| next.is_ascii_digit() || *next == '.' || *next == '_') {
+                position += 1;
+            }
+            let number = chars[start..position].iter().filter(|c| **c != '_').collect::<String>();
+            match number.parse::<i64>() {
+                Ok(value) => Token::Int(value),
+                Err(_) => Token::Float(number.parse::<f64>().map_err(|_| error(start, format!("invalid number `{number}`")))?)
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            while chars.get(position).is_some_and(|next| next.is_alphanumeric() || *next == '_' || *next == '.') {
+                position += 1;
+            }
+            Token::Ident(chars[start..position].iter().collect())
+        } else {
+            let rest = chars[position..].iter().take(2).collect::<String>();
+            let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) else {
+                return Err(error(start, format!("unexpected character `{c}`")));
+            };
+            position += symbol.chars().count();
+            Token::Symbol(symbol)
+        };
+        tokens.push((token, start));
+    }
+    tokens.push((Token::End, chars.len()));
+    Ok(tokens)
+}
+
+// Deeper nesting is rejected rather than risking the stack
+const MAX_DEPTH: usize = 64;
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn position(&self) -> usize {
+        self.tokens[self.index].1
+    }
+
+    fn next(&mut self) -> (Token, usize) {
+        let token = self.tokens[self.index].clone();
+        if token.0 != Token::End {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> DiasError {
+        error(self.position(), format!("expected {expected}, found {}", self.peek().describe()))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().is_keyword(keyword) {
+            self.index += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect_symbol(&mut self, symbol: &str, expected: &str) -> DiasResult<()> {
+        if matches!(self.peek(), Token::Symbol(found) if *found == symbol) {
+            self.index += 1;
+            return Ok(());
+        }
+        Err(self.unexpected(expected))
+    }
+
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Parser) -> DiasResult<T>) -> DiasResult<T> {
+        if self.depth == MAX_DEPTH {
+            return Err(error(self.position(), format!("query is nested more than {MAX_DEPTH} levels deep")));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_or(&mut self) -> DiasResult<Condition> {
+        let mut condition = self.parse_and()?;
+        while self.eat_keyword("or") {
+            condition = condition.or(self.parse_and()?);
+        }
+        Ok(condition)
+    }
+
+    fn parse_and(&mut self) -> DiasResult<Condition> {
+        let mut condition = self.parse_unary()?;
+        while self.eat_keyword("and") {
+            condition = condition.and(self.parse_unary()?);
+        }
+        Ok(condition)
+    }
+
+    fn parse_unary(&mut self) -> DiasResult<Condition> {
+        if self.eat_keyword("not") {
+            return Ok(self.nested(Parser::parse_unary)?.not());
+        }
+        if matches!(self.peek(), Token::Symbol("(")) {
+            // `(` opens either a group of conditions or a computed operand such as
+            // `(price - discount) * qty > 100`, the attempt going furthest wins
+            let start = self.index;
+            self.index += 1;
+            let group = self.nested(Parser::parse_or).and_then(|condition| {
+                self.expect_symbol(")", "`)` to close the group")?;
+                Ok(condition)
+            });
+            let Err(group_error) = group else {
+                return group;
+            };
+            self.index = start;
+            return self.parse_comparison().map_err(|comparison_error| {
+                match (&group_error, &comparison_error) {
+                    (DiasError::Query { position: group, .. }, DiasError::Query { position: comparison, .. })
+                        if comparison > group => comparison_error,
+                    _ => group_error
+                }
+            });
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_column(&mut self) -> DiasResult<String> {
+        match self.peek().clone() {
+            Token::Ident(ident) if !is_reserved(&ident) => {
+                self.index += 1;
+                Ok(ident)
+            },
+            Token::Quoted(ident) => {
+                self.index += 1;
+                Ok(ident)
+            },
+            _ => Err(self.unexpected("a column name"))
+        }
+    }
+
+    fn parse_string(&mut self) -> DiasResult<String> {
+        match self.next() {
+            (Token::Str(value), _) => Ok(value),
+            (token, position) => Err(error(position, format!("expected a string, found {}", token.describe())))
+        }
+    }
+
+    fn parse_literal(&mut self) -> DiasResult<(AnyValue<'static>, usize)> {
+        let (token, position) = self.next();
+        let value = match token {
+            Token::Int(value) => AnyValue::Int64(value),
+            Token::Float(value) => AnyValue::Float64(value),
+            Token::Str(value) => AnyValue::StringOwned(value.into()),
+            Token::Symbol("-") => match self.next() {
+                (Token::Int(value), _) => AnyValue::Int64(-value),
+                (Token::Float(value), _) => AnyValue::Float64(-value),
+                (token, position) => return Err(error(position, format!("expected a number, found {}", token.describe())))
+            },
+            token if token.is_keyword("true") => AnyValue::Boolean(true),
+            token if token.is_keyword("false") => AnyValue::Boolean(false),
+            token => return Err(error(position, format!("expected a value, found {}", token.describe())))
+        };
+        Ok((value, position))
+    }
+
+    // operand := term (("+" | "-") term)*
+    fn parse_operand(&mut self) -> DiasResult<Operand> {
+        let mut operand = self.parse_term()?;
+        while let Some(op) = self.eat_operator(&[("+", Operator::Plus), ("-", Operator::Minus)]) {
+            operand = Operand::Computed(binary_expr(operand.into_expr(), op, self.parse_term()?.into_expr()));
+        }
+        Ok(operand)
+    }
+
+    // term := factor (("*" | "/" | "%") factor)*
+    fn parse_term(&mut self) -> DiasResult<Operand> {
+        let mut operand = self.parse_factor()?;
+        let operators = [("*", Operator::Multiply), ("/", Operator::TrueDivide), ("%", Operator::Modulus)];
+        while let Some(op) = self.eat_operator(&operators) {
+            operand = Operand::Computed(binary_expr(operand.into_expr(), op, self.parse_factor()?.into_expr()));
+        }
+        Ok(operand)
+    }
+
+    fn eat_operator(&mut self, operators: &[(&str, Operator)]) -> Option<Operator> {
+        let Token::Symbol(symbol) = self.peek() else {
+            return None;
+        };
+        let (_, op) = operators.iter().find(|(candidate, _)| candidate == symbol)?;
+        self.index += 1;
+        Some(*op)
+    }
+
+    // factor := column | literal | function "(" operand ")" | "(" operand ")"
+    fn parse_factor(&mut self) -> DiasResult<Operand> {
+        let is_call = matches!(self.tokens.get(self.index + 1), Some((Token::Symbol("("), _)));
+        match self.peek().clone() {
+            Token::Ident(name) if is_call && !is_reserved(&name) => {
+                let name = name.to_ascii_lowercase();
+                let Some(function) = Function::from_name(&name) else {
+                    return Err(error(self.position(), format!("unknown function `{name}`")));
+                };
+                self.index += 2;
+                let argument = self.nested(Parser::parse_operand)?;
+                self.expect_symbol(")", "`)` to close the function call")?;
+                Ok(match argument {
+                    Operand::Column(column) => Operand::Call(function, column),
+                    argument => Operand::Computed(function.apply(argument.into_expr()))
+                })
+            },
+            Token::Ident(ident) if !is_reserved(&ident) => Ok(Operand::Column(self.parse_column()?)),
+            Token::Quoted(_) => Ok(Operand::Column(self.parse_column()?)),
+            Token::Symbol("(") => {
+                self.index += 1;
+                let operand = self.nested(Parser::parse_operand)?;
+                self.expect_symbol(")", "`)` to close the parenthesis")?;
+                Ok(Operand::Computed(operand.into_expr()))
+            },
+            _ => {
+                let (value, _) = self.parse_literal()?;
+                Ok(Operand::Computed(match value {
+                    AnyValue::Int64(value) => lit(value),
+                    AnyValue::Float64(value) => lit(value),
+                    AnyValue::Boolean(value) => lit(value),
+                    AnyValue::StringOwned(value) => lit(value.to_string()),
+                    value => lit(Scalar::new(value.dtype(), value))
+                }))
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> DiasResult<Expr> {
+        Ok(self.parse_operand()?.into_expr())
+    }
+
+    fn parse_list(&mut self) -> DiasResult<Expr> {
+        let start = self.position();
+        self.expect_symbol("[", "`[` to open a list")?;
+        let mut values = Vec::new();
+        if !matches!(self.peek(), Token::Symbol("]")) {
+            loop {
+                values.push(self.parse_literal()?);
+                if matches!(self.peek(), Token::Symbol(",")) {
+                    self.index += 1;
+                    continue;
+                }
+                break;
+            }
+        }
+        self.expect_symbol("]", "`,` or `]` to close the list")?;
+
+        if let Some((_, position)) = values.iter().find(|(value, _)| {
+            std::mem::discriminant(value) != std::mem::discriminant(&values[0].0)
+                && !(value.is_numeric() && values[0].0.is_numeric())
+        }) {
+            return Err(error(*position, "list values must all have the same type"));
+        }
+        let values = values.into_iter().map(|(value, _)| value).collect::<Vec<_>>();
+        let series = Series::from_any_values("".into(), &values, false)
+            .map_err(|err| error(start, err.to_string()))?;
+        Ok(lit(series))
+    }
+
+    fn parse_comparison(&mut self) -> DiasResult<Condition> {
+        let subject = self.parse_operand()?;
+        let position = self.position();
+        let token = self.next().0;
+
+        let operator = if let Some(op) = comparison(&token) {
+            let value = self.parse_value()?;
+            // `len(column)` and date parts keep their dedicated operators
+            if let Operand::Call(function, column) = subject {
+                let operator = match function {
+                    Function::Len => FilterOperators::Length(op, value),
+                    Function::DatePart(part) => FilterOperators::DatePart { part, operator: op, value }
+                };
+                return Ok(Condition::Clause(Clause { column, operator }));
+            }
+            FilterOperators::PolarsOperator(op, value)
+        } else {
+            match token {
+                Token::Ident(keyword) => match keyword.to_ascii_lowercase().as_str() {
+                    "is" => {
+                        let function = if self.eat_keyword("not") { BooleanFunction::IsNotNull } else { BooleanFunction::IsNull };
+                        if !self.eat_keyword("null") {
+                            return Err(self.unexpected("`null`"));
+                        }
+                        FilterOperators::PolarsBooleanFunction(function)
+                    },
+                    "between" => {
+                        let low = self.parse_value()?;
+                        if !self.eat_keyword("and") {
+                            return Err(self.unexpected("`and` between the bounds"));
+                        }
+                        FilterOperators::Between { low, high: self.parse_value()?, inclusive: true }
+                    },
+                    "in" => FilterOperators::IsIn(self.parse_list()?),
+                    "not_in" => FilterOperators::NotIn(self.parse_list()?),
+                    "not" => {
+                        if !self.eat_keyword("in") {
+                            return Err(self.unexpected("`in` after `not`, or use `not_in`"));
+                        }
+                        FilterOperators::NotIn(self.parse_list()?)
+                    },
+                    "contains" => FilterOperators::Contains { pattern: self.parse_string()?, literal: true, strict: true },
+                    "not_contains" => FilterOperators::NotContains { pattern: self.parse_string()?, literal: true, strict: true },
+                    "icontains" => FilterOperators::ContainsIgnoreCase { pattern: self.parse_string()?, literal: true },
+                    "iequals" => FilterOperators::EqualsIgnoreCase(self.parse_string()?),
+                    "matches" => FilterOperators::Contains { pattern: self.parse_string()?, literal: false, strict: true },
+                    "imatches" => FilterOperators::ContainsIgnoreCase { pattern: self.parse_string()?, literal: false },
+                    "starts_with" => FilterOperators::StartsWith(self.parse_string()?),
+                    "ends_with" => FilterOperators::EndsWith(self.parse_string()?),
+                    "not_starts_with" => FilterOperators::NotStartsWith(self.parse_string()?),
+                    "not_ends_with" => FilterOperators::NotEndsWith(self.parse_string()?),
+                    _ => return Err(error(position, format!("unknown operator `{keyword}`")))
+                },
+                token => return Err(error(position, format!("expected an operator, found {}", token.describe())))
+            }
+        };
+        Ok(match subject {
+            Operand::Column(column) => Condition::Clause(Clause { column, operator }),
+            subject => Condition::ExprClause(ExprClause { expr: subject.into_expr(), operator })
+        })
+    }
+}
+
+fn comparison(token: &Token) -> Option<Operator> {
+    match token {
+        Token::Symbol("=") | Token::Symbol("==") => Some(Operator::Eq),
+        Token::Symbol("!=") | Token::Symbol("<>") => Some(Operator::NotEq),
+        Token::Symbol("<") => Some(Operator::Lt),
+        Token::Symbol("<=") => Some(Operator::LtEq),
+        Token::Symbol(">") => Some(Operator::Gt),
+        Token::Symbol(">=") => Some(Operator::GtEq),
+        _ => None
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Function {
+    Len,
+    DatePart(DatePart),
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        match name {
+            "len" => Some(Function::Len),
+            "year" => Some(Function::DatePart(DatePart::Year)),
+            "month" => Some(Function::DatePart(DatePart::Month)),
+            "day" => Some(Function::DatePart(DatePart::Day)),
+            "weekday" => Some(Function::DatePart(DatePart::Weekday)),
+            _ => None
+        }
+    }
+
+    fn apply(self, expr: Expr) -> Expr {
+        match self {
+            Function::Len => length(expr),
+            Function::DatePart(part) => date_part(expr, part)
+        }
+    }
+}
+
+// Either side of a comparison. Plain columns and functions of a plain column compile
+// into a `Clause`, anything else into an `ExprClause`
+enum Operand {
+    Column(String),
+    Call(Function, String),
+    Computed(Expr),
+}
+
+impl Operand {
+    fn into_expr(self) -> Expr {
+        match self {
+            Operand::Column(column) => col(column),
+            Operand::Call(function, column) => function.apply(col(column)),
+            Operand::Computed(expr) => expr
+        }
+    }
+}
+
+const RESERVED: [&str; 6] = ["and", "or", "not", "true", "false", "null"];
+
+fn is_reserved(ident: &str) -> bool {
+    RESERVED.iter().any(|reserved| reserved.eq_ignore_ascii_case(ident))
+}
+
+pub fn parse(query: &str) -> DiasResult<Condition> {
+    let mut parser = Parser { tokens: tokenize(query)?, index: 0, depth: 0 };
+    let condition = parser.parse_or()?;
+    if *parser.peek() != Token::End {
+        return Err(parser.unexpected("`and`, `or` or end of query"));
+    }
+    Ok(condition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::filter::Filter;
+    use crate::transform::transformer::Transformation;
+
+    fn make_df() -> DataFrame {
+        df!(
+            "name" => &["Alice", "Bob", "Bonnie", "Charlie", "Dan"],
+            "amount" => &[Some(100), Some(250), Some(50), None, Some(400)],
+            "country" => &["FR", "US", "MA", "FR", "DE"]
+        ).unwrap()
+    }
+
+    fn run(query: &str) -> DataFrame {
+        let filter = Filter::new(parse(query).unwrap());
+        filter.apply(make_df().lazy()).unwrap().collect().unwrap()
+    }
+
+    fn names(df: &DataFrame) -> Vec<&str> {
+        df.column("name").unwrap().str().unwrap().into_no_null_iter().collect()
+    }
+
+    #[test]
+    fn test_can_parse_grouped_query() {
+        let result = run(r#"amount >= 100 and (name starts_with "Bo" or country in ["FR","MA"])"#);
+
+        assert_eq!(names(&result), ["Alice", "Bob"]);
+    }
+
+    #[test]
+    fn test_precedence_and_negation() {
+        let result = run(r#"country = "FR" or amount > 200 and not name ends_with 'n'"#);
+
+        assert_eq!(names(&result), ["Alice", "Bob", "Charlie"]);
+    }
+
+    #[test]
+    fn test_can_compare_columns_and_nulls() {
+        let df = df!(
+            "a" => &[Some(1), Some(5), None],
+            "b" => &[2, 3, 4]
+        ).unwrap();
+
+        let filter = Filter::new(parse("a is not null and `a` < b").unwrap());
+        let result = filter.apply(df.lazy()).unwrap().collect().unwrap();
+        let expected = df!(
+            "a" => &[Some(1)],
+            "b" => &[2]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_can_parse_extended_operators() {
+        let result = run(r#"amount between 50 and 250 and name not_in ["Alice"] and len(name) > 3"#);
+        assert_eq!(names(&result), ["Bonnie"]);
+
+        let result = run(r#"name icontains "BO" or country iequals "de""#);
+        assert_eq!(names(&result), ["Bob", "Bonnie", "Dan"]);
+    }
+
+    #[test]
+    fn test_can_parse_expression_operands() {
+        let result = run("amount * 2 - len(name) > amount + 100");
+        assert_eq!(names(&result), ["Bob", "Dan"]);
+
+        let result = run(r#"(amount - 50) * 2 >= 300 or (len(name) = 6 and country != "US")"#);
+        assert_eq!(names(&result), ["Bob", "Bonnie", "Dan"]);
+
+        assert!(matches!(parse("len(name) > 3").unwrap(), Condition::Clause(_)));
+        assert!(matches!(parse("len(name) + 1 > 3").unwrap(), Condition::ExprClause(_)));
+    }
+
+    #[test]
+    fn test_errors_point_at_token() {
+        let cases = [
+            ("amount >= and", 10, "expected a value, found `and`"),
+            ("(amount > 1", 11, "expected `)` to close the group, found end of query"),
+            ("name like \"A\"", 5, "unknown operator `like`"),
+            ("country in [\"FR\", 1]", 18, "list values must all have the same type"),
+            ("name = \"Bob", 7, "unterminated \" quote"),
+            ("size(name) > 1", 0, "unknown function `size`"),
+            ("amount between 1 or 2", 17, "expected `and` between the bounds, found `or`"),
+            ("(amount + 1 > 2", 15, "expected `)` to close the group, found end of query"),
+            ("amount * > 2", 9, "expected a value, found `>`"),
+            ("name not like \"A\"", 9, "expected `in` after `not`, or use `not_in`, found `like`"),
+        ];
+
+        for (query, expected_position, expected_reason) in cases {
+            match parse(query) {
+                Err(DiasError::Query { position, reason }) => {
+                    assert_eq!((position, reason.as_str()), (expected_position, expected_reason), "{query}");
+                },
+                _ => panic!("expected `{query}` to be rejected")
+            }
+        }
+    }
+
+    #[test]
+    fn test_can_parse_not_in() {
+        let result = run("country not in [\"FR\"]");
+
+        assert_eq!(names(&result), ["Bob", "Bonnie", "Dan"]);
+    }
+
+    #[test]
+    fn test_deep_nesting_is_rejected() {
+        let nested = |depth: usize, open: &str| format!("{}amount > 1{}", open.repeat(depth), ")".repeat(depth));
+
+        assert!(parse(&nested(MAX_DEPTH, "(")).is_ok());
+        assert!(parse(&format!("{}amount > 1", "not ".repeat(MAX_DEPTH))).is_ok());
+        for query in [nested(100_000, "("), format!("{}amount > 1", "not ".repeat(100_000)), format!("len({}amount{}) > 1", "(".repeat(100_000), ")".repeat(100_000))] {
+            assert!(matches!(parse(&query), Err(DiasError::Query { reason, .. }) if reason.contains("nested")));
+        }
+    }
+}
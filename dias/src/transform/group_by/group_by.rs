@@ -1,53 +1,231 @@
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::error::{DiasError, DiasResult};
+use crate::transform::filter::clause::Clause;
 use crate::transform::transformer::Transformation;
 
-struct Aggregation {
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum AggFunction {
+    Sum(String),
+    Mean(String),
+    Median(String),
+    Min(String),
+    Max(String),
+    // Nulls are only counted when `include_nulls` is set
+    Count { column: String, include_nulls: bool },
+    NUnique(String),
+    First(String),
+    Last(String),
+    Std { column: String, ddof: u8 },
+    Var { column: String, ddof: u8 },
+    Quantile { column: String, quantile: f64, interpolation: QuantileInterpolOptions },
+    // Ties go to the smallest value so the result does not depend on row order
+    Mode(String),
+    StringConcat { column: String, separator: String, ignore_nulls: bool },
+    // Collects the values of each group into a list
+    List(String),
+    // Number of rows in the group matching the clause
+    CountIf(Clause),
+    // Escape hatch for anything polars can express
+    Polars(AggExpr),
+}
+
+// Dtypes an aggregation accepts, checked before the plan is built
+enum Accepts {
+    Any,
+    Numeric,
+    NumericOrTemporal,
+    Ordered,
+    String,
+}
+
+impl Accepts {
+    fn matches(&self, dtype: &DataType) -> bool {
+        match self {
+            Accepts::Any => true,
+            Accepts::Numeric => dtype.is_numeric(),
+            Accepts::NumericOrTemporal => dtype.is_numeric() || dtype.is_temporal(),
+            Accepts::Ordered => dtype.is_numeric() || dtype.is_temporal() || dtype.is_bool() || dtype == &DataType::String,
+            Accepts::String => dtype == &DataType::String
+        }
+    }
+}
+
+impl AggFunction {
+    fn name(&self) -> &'static str {
+        match self {
+            AggFunction::Sum(_) => "sum",
+            AggFunction::Mean(_) => "mean",
+            AggFunction::Median(_) => "median",
+            AggFunction::Min(_) => "min",
+            AggFunction::Max(_) => "max",
+            AggFunction::Count { .. } => "count",
+            AggFunction::NUnique(_) => "n_unique",
+            AggFunction::First(_) => "first",
+            AggFunction::Last(_) => "last",
+            AggFunction::Std { .. } => "std",
+            AggFunction::Var { .. } => "var",
+            AggFunction::Quantile { .. } => "quantile",
+            AggFunction::Mode(_) => "mode",
+            AggFunction::StringConcat { .. } => "string_concat",
+            AggFunction::List(_) => "list",
+            AggFunction::CountIf(_) => "count_if",
+            AggFunction::Polars(_) => "polars"
+        }
+    }
+
+    // The column read by the aggregation and the dtypes it accepts
+    fn input(&self) -> Option<(&str, Accepts)> {
+        let input = match self {
+            AggFunction::Sum(column) => (column, Accepts::Numeric),
+            AggFunction::Mean(column) | AggFunction::Median(column) => (column, Accepts::NumericOrTemporal),
+            AggFunction::Min(column) | AggFunction::Max(column) => (column, Accepts::Ordered),
+            AggFunction::Std { column, .. } | AggFunction::Var { column, .. } | AggFunction::Quantile { column, .. } => (column, Accepts::Numeric),
+            AggFunction::StringConcat { column, .. } => (column, Accepts::String),
+            AggFunction::Count { column, .. }
+            | AggFunction::NUnique(column)
+            | AggFunction::First(column)
+            | AggFunction::Last(column)
+            | AggFunction::Mode(column)
+            | AggFunction::List(column) => (column, Accepts::Any),
+            AggFunction::CountIf(clause) => (&clause.column, Accepts::Any),
+            AggFunction::Polars(_) => return None
+        };
+        Some((input.0.as_str(), input.1))
+    }
+
+    // `step` names the step in error messages
+    fn validate(&self, schema: &Schema, step: &'static str) -> DiasResult<()> {
+        let Some((column, accepts)) = self.input() else {
+            return Ok(());
+        };
+        let Some(dtype) = schema.get(column) else {
+            return Err(DiasError::invalid_parameter(step, "aggregations", format!("column `{column}` does not exist")));
+        };
+        if !accepts.matches(dtype) {
+            return Err(DiasError::invalid_parameter(
+                step,
+                "aggregations",
+                format!("`{}` cannot be applied to column `{column}` of type {dtype}", self.name())
+            ));
+        }
+        if let AggFunction::Quantile { quantile, .. } = self {
+            if !(0.0..=1.0).contains(quantile) {
+                return Err(DiasError::invalid_parameter(step, "quantile", format!("expected a value between 0 and 1, found {quantile}")));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn make_expr(&self) -> DiasResult<Expr> {
+        let expr = match self {
+            AggFunction::Sum(column) => col(column).sum(),
+            AggFunction::Mean(column) => col(column).mean(),
+            AggFunction::Median(column) => col(column).median(),
+            AggFunction::Min(column) => col(column).min(),
+            AggFunction::Max(column) => col(column).max(),
+            AggFunction::Count { column, include_nulls } => {
+                if *include_nulls {
+                    col(column).len()
+                } else {
+                    col(column).count()
+                }
+            },
+            AggFunction::NUnique(column) => col(column).n_unique(),
+            AggFunction::First(column) => col(column).first(),
+            AggFunction::Last(column) => col(column).last(),
+            AggFunction::Std { column, ddof } => col(column).std(*ddof),
+            AggFunction::Var { column, ddof } => col(column).var(*ddof),
+            AggFunction::Quantile { column, quantile, interpolation } => col(column).quantile(lit(*quantile), *interpolation),
+            AggFunction::Mode(column) => col(column).mode().sort(Default::default()).first(),
+            AggFunction::StringConcat { column, separator, ignore_nulls } => col(column).str().join(separator, *ignore_nulls),
+            // A bare column in an aggregation context already yields the group's values as a list
+            AggFunction::List(column) => col(column),
+            AggFunction::CountIf(clause) => clause.make_expr()?.sum(),
+            AggFunction::Polars(aggregation) => Expr::Agg(aggregation.clone())
+        };
+        Ok(expr)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Aggregation {
     new_column: String,
-    aggregation: AggExpr
+    aggregation: AggFunction
 }
 
-struct GroupBy {
+impl Aggregation {
+    pub fn new(new_column: impl Into<String>, aggregation: AggFunction) -> Aggregation {
+        Aggregation { new_column: new_column.into(), aggregation }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GroupBy {
     grouping: Vec<Expr>,
     aggregations: Vec<Aggregation>
 }
 
-impl GroupBy {
-    fn make_aggregation_expr(self) -> Vec<Expr> {
-        self.aggregations
-            .into_iter()
-            .map(|agg| Expr::Agg(agg.aggregation.clone()).alias(&agg.new_column))
-            .collect::<Vec<_>>()
-    }
+pub(crate) fn make_aggregation_exprs(aggregations: &[Aggregation], schema: &Schema, step: &'static str) -> DiasResult<Vec<Expr>> {
+    aggregations
+        .iter()
+        .map(|agg| {
+            agg.aggregation.validate(schema, step)?;
+            Ok(agg.aggregation.make_expr()?.alias(&agg.new_column))
+        })
+        .collect()
 }
 
 impl Transformation for GroupBy {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
-        df.group_by(&self.grouping)
-          .agg(self.make_aggregation_expr())
+    fn apply(&self, mut df: LazyFrame) -> DiasResult<LazyFrame> {
+        let aggregations = make_aggregation_exprs(&self.aggregations, &*df.collect_schema()?, "GroupBy")?;
+        Ok(df.group_by(&self.grouping)
+          .agg(aggregations))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
-    #[test]
-    fn test_can_aggregate_on_group_by() {
-        let df = df!(
+    use crate::transform::filter::clause::FilterOperators;
+
+    fn make_df() -> DataFrame {
+        df!(
             "customer_id" => &[1, 2, 2, 3],
             "name" => &["Alice", "Bob", "Bob", "Charlie"],
             "order_id"=> &[Some("a"), Some("b"), Some("c"), None],
             "amount"=> &[Some(100), Some(200), Some(300), None],
-        ).unwrap();
+        ).unwrap()
+    }
+
+    fn aggregate(aggregations: Vec<Aggregation>) -> DiasResult<DataFrame> {
+        let transformation = GroupBy {
+            grouping: vec!["name".into()],
+            aggregations
+        };
+        Ok(transformation.apply(make_df().lazy())?
+            .sort(["name"], Default::default())
+            .collect()?)
+    }
+
+    #[test]
+    fn test_can_aggregate_on_group_by() {
+        let df = make_df();
         let aggregations: Vec<Aggregation> = vec![
-                Aggregation { new_column: String::from("Sum"), aggregation: AggExpr::Sum(Arc::new("amount".into())) }            
+                Aggregation { new_column: String::from("Sum"), aggregation: AggFunction::Polars(AggExpr::Sum(Arc::new("amount".into()))) }
         ];
         let transformation = GroupBy {
             grouping: vec!["name".into()],
             aggregations
         };
-        let result = transformation.apply(df.lazy()).collect().unwrap();
+        // Groups come back in no particular order
+        let result = transformation.apply(df.lazy())
+            .unwrap()
+            .sort(["name"], Default::default())
+            .collect()
+            .unwrap();
         let expected = df!(
             "name" => &["Alice", "Bob", "Charlie"],
             "Sum" => &[100, 500, 0]
@@ -55,4 +233,72 @@ mod tests {
 
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn test_catalogue_aggregations() {
+        let result = aggregate(vec![
+            Aggregation { new_column: String::from("total"), aggregation: AggFunction::Sum(String::from("amount")) },
+            Aggregation { new_column: String::from("orders"), aggregation: AggFunction::Count { column: String::from("order_id"), include_nulls: false } },
+            Aggregation { new_column: String::from("rows"), aggregation: AggFunction::Count { column: String::from("order_id"), include_nulls: true } },
+            Aggregation {
+                new_column: String::from("order_ids"),
+                aggregation: AggFunction::StringConcat { column: String::from("order_id"), separator: String::from(","), ignore_nulls: true }
+            },
+            Aggregation {
+                new_column: String::from("large"),
+                aggregation: AggFunction::CountIf(Clause {
+                    column: String::from("amount"),
+                    operator: FilterOperators::PolarsOperator(Operator::GtEq, lit(200))
+                })
+            },
+            Aggregation {
+                new_column: String::from("median"),
+                aggregation: AggFunction::Quantile { column: String::from("amount"), quantile: 0.5, interpolation: QuantileInterpolOptions::Linear }
+            },
+        ]).unwrap();
+        let expected = df!(
+            "name" => &["Alice", "Bob", "Charlie"],
+            "total" => &[100, 500, 0],
+            "orders" => &[1u32, 2, 0],
+            "rows" => &[1u32, 2, 1],
+            "order_ids" => &["a", "b,c", ""],
+            "large" => &[0u32, 2, 0],
+            "median" => &[Some(100.0), Some(250.0), None]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_collects_lists_and_modes() {
+        let result = aggregate(vec![
+            Aggregation { new_column: String::from("ids"), aggregation: AggFunction::List(String::from("customer_id")) },
+            Aggregation { new_column: String::from("mode"), aggregation: AggFunction::Mode(String::from("order_id")) },
+            Aggregation { new_column: String::from("last"), aggregation: AggFunction::Last(String::from("amount")) },
+        ]).unwrap();
+
+        assert_eq!(result.column("ids").unwrap().list().unwrap().get_as_series(1).unwrap().i32().unwrap().to_vec(), [Some(2), Some(2)]);
+        assert_eq!(result.column("mode").unwrap().str().unwrap().into_iter().collect::<Vec<_>>(), [Some("a"), Some("b"), None]);
+        assert_eq!(result.column("last").unwrap().i32().unwrap().to_vec(), [Some(100), Some(300), None]);
+    }
+
+    #[test]
+    fn test_aggregations_are_validated() {
+        let dtype = aggregate(vec![
+            Aggregation { new_column: String::from("total"), aggregation: AggFunction::Mean(String::from("name")) }
+        ]);
+        let missing = aggregate(vec![
+            Aggregation { new_column: String::from("total"), aggregation: AggFunction::Sum(String::from("missing")) }
+        ]);
+        let quantile = aggregate(vec![
+            Aggregation {
+                new_column: String::from("p"),
+                aggregation: AggFunction::Quantile { column: String::from("amount"), quantile: 1.5, interpolation: Default::default() }
+            }
+        ]);
+
+        assert!(matches!(dtype, Err(DiasError::InvalidParameter { step: "GroupBy", parameter: "aggregations", .. })));
+        assert!(matches!(missing, Err(DiasError::InvalidParameter { step: "GroupBy", parameter: "aggregations", .. })));
+        assert!(matches!(quantile, Err(DiasError::InvalidParameter { step: "GroupBy", parameter: "quantile", .. })));
+    }
 }
@@ -1 +1,3 @@
-mod group_by;
+#[allow(clippy::module_inception)]
+pub mod group_by;
+pub mod window;
@@ -0,0 +1,302 @@
+use std::sync::LazyLock;
+
+use polars::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DiasError, DiasResult};
+use crate::transform::transformer::Transformation;
+use super::group_by::{make_aggregation_exprs, Aggregation};
+
+#[derive(Serialize, Deserialize)]
+pub enum Window {
+    // Windows started every `every` and lasting `period`, which defaults to `every`, e.g. hourly or daily buckets
+    Dynamic {
+        every: String,
+        period: Option<String>,
+        offset: Option<String>,
+        label: Label,
+        #[serde(default)]
+        start_by: StartBy,
+        #[serde(default)]
+        include_boundaries: bool,
+    },
+    // One window per row covering the `period` before it, e.g. a 7-day rolling total
+    Rolling { period: String, offset: Option<String> },
+}
+
+// One number and unit pair of a duration, e.g. `15d` in `1mo15d`
+static DURATION_PART: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+)(ns|us|ms|mo|s|m|h|d|w|q|y|i)").expect("duration part is a valid regex"));
+
+// Durations use the polars syntax, e.g. `1h`, `7d` or `1mo15d`, and `i` for integer indexes
+fn parse_duration(duration: &str, parameter: &'static str, integer_index: bool) -> DiasResult<Duration> {
+    let invalid = |reason: &str| DiasError::invalid_parameter("GroupByWindow", parameter, format!("`{duration}` {reason}"));
+
+    // `Duration::parse` panics on invalid input and overflows silently, so every part is checked first
+    let digits = duration.strip_prefix('-').unwrap_or(duration);
+    let parts = DURATION_PART.captures_iter(digits).collect::<Vec<_>>();
+    let covered = parts.iter().map(|part| part[0].len()).sum::<usize>();
+    if parts.is_empty() || covered != digits.len() {
+        return Err(invalid("is not a valid duration"));
+    }
+    if parts.len() > 1 && parts.iter().any(|part| &part[2] == "i") {
+        return Err(invalid("mixes an integer index with other units"));
+    }
+    // Checked here since polars only finds out when the frame is collected
+    match (&parts[0][2] == "i", integer_index) {
+        (true, false) => return Err(invalid("uses `i`, which needs an integer index column")),
+        (false, true) => return Err(invalid("uses calendar units, which need a temporal index column")),
+        _ => {}
+    }
+
+    // Totals kept the same way polars keeps them, each must fit in an i64
+    let (mut nsecs, mut days, mut weeks, mut months) = (0i64, 0i64, 0i64, 0i64);
+    for part in &parts {
+        let count = part[1].parse::<i64>().map_err(|_| invalid("is too large"))?;
+        let (total, scale) = match &part[2] {
+            "ns" | "i" => (&mut nsecs, 1),
+            "us" => (&mut nsecs, 1_000),
+            "ms" => (&mut nsecs, 1_000_000),
+            "s" => (&mut nsecs, 1_000_000_000),
+            "m" => (&mut nsecs, 60_000_000_000),
+            "h" => (&mut nsecs, 3_600_000_000_000),
+            "d" => (&mut days, 1),
+            "w" => (&mut weeks, 1),
+            "mo" => (&mut months, 1),
+            "q" => (&mut months, 3),
+            _ => (&mut months, 12)
+        };
+        *total = count
+            .checked_mul(scale)
+            .and_then(|count| total.checked_add(count))
+            .ok_or_else(|| invalid("is too large"))?;
+    }
+    Ok(Duration::parse(duration))
+}
+
+// Window sizes and steps, unlike offsets, must move forward
+fn parse_length(duration: &str, parameter: &'static str, integer_index: bool) -> DiasResult<Duration> {
+    let length = parse_duration(duration, parameter, integer_index)?;
+    if length.is_zero() || length.negative() {
+        return Err(DiasError::invalid_parameter("GroupByWindow", parameter, format!("`{duration}` must be a positive duration")));
+    }
+    Ok(length)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GroupByWindow {
+    // Date, datetime or integer column the windows are computed on
+    pub index_column: String,
+    pub window: Window,
+    // Defaults to polars' own, left for dynamic windows and right for rolling ones
+    #[serde(default)]
+    pub closed: Option<ClosedWindow>,
+    // Extra keys, windows are computed separately for every combination
+    #[serde(default)]
+    pub by: Vec<String>,
+    pub aggregations: Vec<Aggregation>
+}
+
+impl GroupByWindow {
+    // Whether the index is an integer column, durations are then counted in `i`
+    fn validate(&self, schema: &Schema) -> DiasResult<bool> {
+        let integer_index = match schema.get(&self.index_column) {
+            Some(DataType::Date | DataType::Datetime(..)) => false,
+            Some(DataType::Int32 | DataType::Int64) => true,
+            Some(dtype) => return Err(DiasError::invalid_parameter(
+                "GroupByWindow",
+                "index_column",
+                format!("column `{}` of type {dtype} is neither temporal nor an integer", self.index_column)
+            )),
+            None => return Err(DiasError::invalid_parameter("GroupByWindow", "index_column", format!("column `{}` does not exist", self.index_column)))
+        };
+        if let Some(missing) = self.by.iter().find(|name| !schema.contains(name)) {
+            return Err(DiasError::invalid_parameter("GroupByWindow", "by", format!("column `{missing}` does not exist")));
+        }
+        Ok(integer_index)
+    }
+}
+
+impl Transformation for GroupByWindow {
+    fn apply(&self, mut df: LazyFrame) -> DiasResult<LazyFrame> {
+        let schema = df.collect_schema()?;
+        let integer_index = self.validate(&schema)?;
+        let aggregations = make_aggregation_exprs(&self.aggregations, &schema, "GroupByWindow")?;
+
+        let index = col(&self.index_column);
+        let by = self.by.iter().map(col).collect::<Vec<_>>();
+        // Windows need the index sorted within each group, sorting on the index alone is enough
+        let df = df.sort([self.index_column.as_str()], Default::default());
+
+        let grouped = match &self.window {
+            Window::Dynamic { every, period, offset, label, start_by, include_boundaries } => {
+                let every = parse_length(every, "every", integer_index)?;
+                let options = DynamicGroupOptions {
+                    every,
+                    period: period.as_deref().map(|period| parse_length(period, "period", integer_index)).transpose()?.unwrap_or(every),
+                    offset: offset.as_deref().map(|offset| parse_duration(offset, "offset", integer_index)).transpose()?.unwrap_or(Duration::parse("0ns")),
+                    label: *label,
+                    include_boundaries: *include_boundaries,
+                    closed_window: self.closed.unwrap_or(ClosedWindow::Left),
+                    start_by: *start_by,
+                    ..Default::default()
+                };
+                df.group_by_dynamic(index, by, options)
+            },
+            Window::Rolling { period, offset } => {
+                let period = parse_length(period, "period", integer_index)?;
+                let options = RollingGroupOptions {
+                    period,
+                    // By default the window covers `period` up to and including the row itself
+                    offset: offset.as_deref().map(|offset| parse_duration(offset, "offset", integer_index)).transpose()?.unwrap_or(-period),
+                    closed_window: self.closed.unwrap_or(ClosedWindow::Right),
+                    ..Default::default()
+                };
+                df.rolling(index, by, options)
+            }
+        };
+        Ok(grouped.agg(aggregations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveDateTime};
+    use crate::transform::group_by::group_by::AggFunction;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn make_df() -> DataFrame {
+        df!(
+            "time" => &[at(1, 9), at(1, 10), at(2, 8), at(1, 9), at(3, 12)],
+            "store" => &["a", "a", "a", "b", "a"],
+            "sales" => &[1, 2, 3, 10, 4]
+        ).unwrap()
+    }
+
+    fn total() -> Vec<Aggregation> {
+        vec![Aggregation::new("total", AggFunction::Sum(String::from("sales")))]
+    }
+
+    #[test]
+    fn test_daily_windows_per_key() {
+        let transformation = GroupByWindow {
+            index_column: String::from("time"),
+            window: Window::Dynamic {
+                every: String::from("1d"),
+                period: None,
+                offset: None,
+                label: Label::Left,
+                start_by: StartBy::WindowBound,
+                include_boundaries: false
+            },
+            closed: Some(ClosedWindow::Left),
+            by: vec![String::from("store")],
+            aggregations: total()
+        };
+
+        let result = transformation.apply(make_df().lazy()).unwrap()
+            .sort(["store", "time"], Default::default())
+            .collect()
+            .unwrap();
+        let expected = df!(
+            "store" => &["a", "a", "a", "b"],
+            "time" => &[at(1, 0), at(2, 0), at(3, 0), at(1, 0)],
+            "total" => &[3, 3, 4, 10]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_rolling_windows() {
+        let transformation = GroupByWindow {
+            index_column: String::from("time"),
+            window: Window::Rolling { period: String::from("1d"), offset: None },
+            closed: Some(ClosedWindow::Right),
+            by: vec![],
+            aggregations: total()
+        };
+
+        let result = transformation.apply(make_df().lazy()).unwrap()
+            .collect()
+            .unwrap();
+
+        // Each row sums the sales of the 24 hours up to and including its own time
+        assert_eq!(result.column("total").unwrap().i32().unwrap().to_vec(), [Some(11), Some(11), Some(13), Some(16), Some(4)]);
+    }
+
+    #[test]
+    fn test_invalid_windows_are_rejected() {
+        let mut transformation = GroupByWindow {
+            index_column: String::from("store"),
+            window: Window::Rolling { period: String::from("1d"), offset: None },
+            closed: Some(ClosedWindow::Right),
+            by: vec![],
+            aggregations: total()
+        };
+
+        let result = transformation.apply(make_df().lazy());
+        assert!(matches!(result, Err(DiasError::InvalidParameter { step: "GroupByWindow", parameter: "index_column", .. })));
+
+        transformation.index_column = String::from("time");
+        for period in ["1 day", "0d", "2i"] {
+            transformation.window = Window::Rolling { period: String::from(period), offset: None };
+            let result = transformation.apply(make_df().lazy());
+            assert!(matches!(result, Err(DiasError::InvalidParameter { step: "GroupByWindow", parameter: "period", .. })), "{period}");
+        }
+
+        transformation.index_column = String::from("sales");
+        transformation.window = Window::Rolling { period: String::from("1d"), offset: None };
+        let result = transformation.apply(make_df().lazy());
+        assert!(matches!(result, Err(DiasError::InvalidParameter { step: "GroupByWindow", parameter: "period", .. })));
+    }
+
+    #[test]
+    fn test_durations_never_panic() {
+        for duration in ["99999999999999999999d", "1000000000000h", "1i2d", "2d1i", "", "-", "1d-2h", "d"] {
+            let result = parse_duration(duration, "every", false);
+            assert!(matches!(result, Err(DiasError::InvalidParameter { step: "GroupByWindow", parameter: "every", .. })), "{duration}");
+        }
+
+        assert_eq!(parse_duration("1mo15d", "every", false).unwrap(), Duration::parse("1mo15d"));
+        assert_eq!(parse_duration("-3i", "offset", true).unwrap(), Duration::parse("-3i"));
+    }
+
+    #[test]
+    fn test_rolling_windows_include_the_row_by_default() {
+        let transformation: GroupByWindow = serde_json::from_value(serde_json::json!({
+            "index_column": "time",
+            "window": {"Rolling": {"period": "1d", "offset": null}},
+            "aggregations": [{"new_column": "total", "aggregation": {"Sum": "sales"}}]
+        })).unwrap();
+
+        let result = transformation.apply(make_df().lazy()).unwrap()
+            .collect()
+            .unwrap();
+
+        assert_eq!(result.column("total").unwrap().i32().unwrap().to_vec(), [Some(11), Some(11), Some(13), Some(16), Some(4)]);
+    }
+
+    #[test]
+    fn test_integer_index_windows() {
+        let transformation = GroupByWindow {
+            index_column: String::from("sales"),
+            window: Window::Rolling { period: String::from("2i"), offset: None },
+            closed: None,
+            by: vec![],
+            aggregations: vec![Aggregation::new("rows", AggFunction::Count { column: String::from("sales"), include_nulls: true })]
+        };
+
+        let result = transformation.apply(make_df().lazy()).unwrap()
+            .collect()
+            .unwrap();
+
+        // Sorted sales are 1, 2, 3, 4 and 10, each window holds the values in (sales - 2, sales]
+        assert_eq!(result.column("rows").unwrap().u32().unwrap().to_vec(), [Some(1), Some(2), Some(2), Some(2), Some(1)]);
+    }
+}
@@ -1,64 +1,73 @@
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::default::Default;
 
+use crate::error::{DiasError, DiasResult};
 use crate::transform::transformer::Transformation;
 
-struct Join<'a> {
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Join {
     strategy: JoinType,
+    #[serde(with = "crate::transform::optional_frame")]
     right: Option<LazyFrame>,
-    left_on: &'a [Expr],
-    right_on: &'a [Expr],
+    left_on: Vec<Expr>,
+    right_on: Vec<Expr>,
     allow_parallel: bool,
     force_parallel: bool,
     join_nulls: bool,
     coalese: JoinCoalesce,
-    suffix: Option<&'a str>,
+    suffix: Option<String>,
 }
 
-impl<'a> Default for Join<'a> {
-    fn default() -> Join<'a> {
+impl Default for Join {
+    fn default() -> Join {
         Join {
             strategy: JoinType::Left,
             right: None,
-            left_on: &[],
-            right_on: &[],
+            left_on: Vec::new(),
+            right_on: Vec::new(),
             allow_parallel: false,
             force_parallel: false,
             join_nulls: false,
             coalese: JoinCoalesce::JoinSpecific,
-            suffix: Some("_right")
+            suffix: Some(String::from("_right"))
         }
     }
 }
 
-impl<'a> Transformation for Join<'a> {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
+impl Transformation for Join {
+    #[allow(clippy::len_zero)]
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame> {
         let mut builder = df.join_builder();
-        match self.right {
+        match &self.right {
             Some(right) => {
-                builder = builder.with(right);
+                builder = builder.with(right.clone());
             },
-            _ => panic!("Expected a right Dataframe")
+            _ => return Err(DiasError::invalid_parameter("Join", "right", "expected a right Dataframe"))
         }
 
-        if self.left_on.len() == 0 || self.right_on.len() == 0 {
-            panic!("No columns to join on");
+        if self.left_on.len() == 0 {
+            return Err(DiasError::invalid_parameter("Join", "left_on", "no columns to join on"));
+        }
+        if self.right_on.len() == 0 {
+            return Err(DiasError::invalid_parameter("Join", "right_on", "no columns to join on"));
         }
 
         builder = builder
-            .how(self.strategy)
-            .left_on(self.left_on)
-            .right_on(self.right_on)
+            .how(self.strategy.clone())
+            .left_on(self.left_on.clone())
+            .right_on(self.right_on.clone())
             .allow_parallel(self.allow_parallel)
             .force_parallel(self.force_parallel)
             .coalesce(self.coalese)
             .join_nulls(self.join_nulls);
 
-        if let Some(suffix) = self.suffix {
+        if let Some(suffix) = &self.suffix {
             builder = builder.suffix(suffix);
         }
 
-        builder.finish()
+        Ok(builder.finish())
     }
 }
 
@@ -80,12 +89,12 @@ mod tests {
         let transformation = Join {
             strategy: JoinType::Left,
             right: Some(df_orders.lazy()),
-            left_on: &[col("customer_id")],
-            right_on: &[col("customer_id")],
+            left_on: vec![col("customer_id")],
+            right_on: vec![col("customer_id")],
             ..Default::default()
         };
 
-        let result = transformation.apply(df_customers.lazy()).collect().unwrap();
+        let result = transformation.apply(df_customers.lazy()).unwrap().collect().unwrap();
         let expected = df!(
             "customer_id" => &[1, 2, 2, 3],
             "name" => &["Alice", "Bob", "Bob", "Charlie"],
@@ -95,4 +104,23 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_missing_right_frame_is_rejected() {
+        let df = df! (
+            "customer_id" => &[1, 2, 3],
+        ).unwrap();
+        let transformation = Join {
+            left_on: vec![col("customer_id")],
+            right_on: vec![col("customer_id")],
+            ..Default::default()
+        };
+
+        let result = transformation.apply(df.lazy());
+
+        assert!(matches!(
+            result,
+            Err(DiasError::InvalidParameter { step: "Join", parameter: "right", .. })
+        ));
+    }
 }
@@ -1 +1,2 @@
-mod join;
+#[allow(clippy::module_inception)]
+pub mod join;
@@ -1,5 +1,8 @@
-mod transformer;
+pub mod transformer;
+mod optional_frame;
+pub mod selector;
 pub mod select_cols;
+pub mod reorder_cols;
 pub mod count_rows;
 pub mod exclude_cols;
 pub mod sort;
@@ -10,3 +13,6 @@ pub mod join;
 pub mod group_by;
 pub mod transpose;
 pub mod text;
+pub mod validate;
+pub mod with_columns;
+pub mod rename_cols;
@@ -0,0 +1,13 @@
+use polars::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// LazyFrame has no serde support of its own, so frames are stored as their logical plan
+pub fn serialize<S: Serializer>(frame: &Option<LazyFrame>, serializer: S) -> Result<S::Ok, S::Error> {
+    frame.as_ref()
+        .map(|frame| &frame.logical_plan)
+        .serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<LazyFrame>, D::Error> {
+    Ok(Option::<DslPlan>::deserialize(deserializer)?.map(LazyFrame::from))
+}
@@ -0,0 +1,221 @@
+use polars::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DiasError, DiasResult};
+use super::transformer::Transformation;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum Case {
+    // `Customer ID` and `customerId` become `customer_id`
+    Snake,
+    // `customer_id` becomes `Customer Id`
+    Title,
+    Lower,
+    Upper,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum RenameRule {
+    Mapping(PlHashMap<String, String>),
+    // Every match is replaced, `$1` style references to capture groups are supported
+    Regex { pattern: String, replacement: String },
+    Prefix(String),
+    Suffix(String),
+    Case(Case),
+}
+
+// Splits on separators, lower to upper case changes and the end of acronyms
+fn words(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1);
+        let boundary = c.is_uppercase() && previous.is_some_and(|previous| {
+            previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next.is_some_and(|next| next.is_lowercase()))
+        });
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(*c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+impl Case {
+    fn apply(&self, name: &str) -> String {
+        match self {
+            Case::Snake => words(name)
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Case::Title => words(name)
+                .iter()
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+                        None => String::new()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            Case::Lower => name.to_lowercase(),
+            Case::Upper => name.to_uppercase()
+        }
+    }
+}
+
+impl RenameRule {
+    fn apply(&self, names: Vec<String>) -> DiasResult<Vec<String>> {
+        let names = match self {
+            RenameRule::Mapping(mapping) => {
+                if let Some(missing) = mapping.keys().find(|old| !names.contains(old)) {
+                    return Err(DiasError::invalid_parameter("RenameCols", "mapping", format!("column `{missing}` does not exist")));
+                }
+                names
+                    .into_iter()
+                    .map(|name| mapping.get(&name).cloned().unwrap_or(name))
+                    .collect()
+            },
+            RenameRule::Regex { pattern, replacement } => {
+                let regex = Regex::new(pattern)
+                    .map_err(|err| DiasError::invalid_parameter("RenameCols", "pattern", err.to_string()))?;
+                names
+                    .into_iter()
+                    .map(|name| regex.replace_all(&name, replacement.as_str()).into_owned())
+                    .collect()
+            },
+            RenameRule::Prefix(prefix) => names.into_iter().map(|name| format!("{prefix}{name}")).collect(),
+            RenameRule::Suffix(suffix) => names.into_iter().map(|name| format!("{name}{suffix}")).collect(),
+            RenameRule::Case(case) => names.iter().map(|name| case.apply(name)).collect()
+        };
+        Ok(names)
+    }
+}
+
+// Rules are applied in order, each one to the names produced by the previous one
+#[derive(Serialize, Deserialize)]
+pub struct RenameCols {
+    pub rules: Vec<RenameRule>
+}
+
+fn check_collisions(names: &[String], renamed: &[String]) -> DiasResult<()> {
+    let mut seen = PlHashMap::with_capacity(names.len());
+    for (old, new) in names.iter().zip(renamed) {
+        if new.is_empty() {
+            return Err(DiasError::invalid_parameter("RenameCols", "rules", format!("column `{old}` would be left without a name")));
+        }
+        if let Some(other) = seen.insert(new, old) {
+            return Err(DiasError::invalid_parameter(
+                "RenameCols",
+                "rules",
+                format!("columns `{other}` and `{old}` would both be renamed to `{new}`")
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl RenameCols {
+    fn rename(&self, names: &[String]) -> DiasResult<Vec<String>> {
+        let renamed = self.rules.iter().try_fold(names.to_vec(), |names, rule| rule.apply(names))?;
+        check_collisions(names, &renamed)?;
+        Ok(renamed)
+    }
+}
+
+impl Transformation for RenameCols {
+    fn apply(&self, mut df: LazyFrame) -> DiasResult<LazyFrame> {
+        let names = df.collect_schema()?
+            .iter_names()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        let renamed = self.rename(&names)?;
+
+        let (old, new): (Vec<_>, Vec<_>) = names
+            .into_iter()
+            .zip(renamed)
+            .filter(|(old, new)| old != new)
+            .unzip();
+        Ok(df.rename(old, new))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_df() -> DataFrame {
+        df!(
+            "Customer ID" => &[1, 2],
+            "orderDate" => &["2024-01-01", "2024-01-02"],
+            "HTTPStatus" => &[200, 404],
+            "amount_right" => &[10, 20]
+        ).unwrap()
+    }
+
+    fn names(transformation: &RenameCols) -> DiasResult<Vec<String>> {
+        let df = transformation.apply(make_df().lazy())?.collect()?;
+        Ok(df.get_column_names().iter().map(|name| name.to_string()).collect())
+    }
+
+    #[test]
+    fn test_can_normalize_case() {
+        let snake = RenameCols { rules: vec![RenameRule::Case(Case::Snake)] };
+        let title = RenameCols { rules: vec![RenameRule::Case(Case::Snake), RenameRule::Case(Case::Title)] };
+
+        assert_eq!(names(&snake).unwrap(), ["customer_id", "order_date", "http_status", "amount_right"]);
+        assert_eq!(names(&title).unwrap(), ["Customer Id", "Order Date", "Http Status", "Amount Right"]);
+    }
+
+    #[test]
+    fn test_rules_apply_in_order() {
+        let transformation = RenameCols {
+            rules: vec![
+                RenameRule::Mapping(PlHashMap::from_iter([(String::from("Customer ID"), String::from("id"))])),
+                RenameRule::Regex { pattern: String::from("^(.*)_right$"), replacement: String::from("${1}_2") },
+                RenameRule::Prefix(String::from("src_")),
+                RenameRule::Suffix(String::from("_v1")),
+            ]
+        };
+
+        assert_eq!(names(&transformation).unwrap(), ["src_id_v1", "src_orderDate_v1", "src_HTTPStatus_v1", "src_amount_2_v1"]);
+    }
+
+    #[test]
+    fn test_collisions_are_rejected() {
+        let transformation = RenameCols {
+            rules: vec![RenameRule::Regex { pattern: String::from(".*"), replacement: String::from("col") }]
+        };
+
+        let result = names(&transformation);
+
+        assert!(matches!(result, Err(DiasError::InvalidParameter { step: "RenameCols", parameter: "rules", .. })));
+    }
+
+    #[test]
+    fn test_unknown_mapping_is_rejected() {
+        let transformation = RenameCols {
+            rules: vec![RenameRule::Mapping(PlHashMap::from_iter([(String::from("missing"), String::from("id"))]))]
+        };
+
+        let result = names(&transformation);
+
+        assert!(matches!(result, Err(DiasError::InvalidParameter { step: "RenameCols", parameter: "mapping", .. })));
+    }
+}
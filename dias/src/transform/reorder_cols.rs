@@ -0,0 +1,152 @@
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DiasError, DiasResult};
+use super::selector::Selector;
+use super::transformer::Transformation;
+
+#[derive(Serialize, Deserialize)]
+pub enum Move {
+    Front(Selector),
+    Back(Selector),
+    Before { columns: Selector, anchor: String },
+    After { columns: Selector, anchor: String },
+    Alphabetical { descending: bool },
+}
+
+// Moves are applied in order, columns that are not mentioned keep their relative order
+#[derive(Serialize, Deserialize)]
+pub struct ReorderCols {
+    pub moves: Vec<Move>
+}
+
+fn resolve(columns: &Selector, order: &[String], schema: &Schema) -> DiasResult<Vec<String>> {
+    // Selectors are resolved against the current order rather than the original one
+    let current = order
+        .iter()
+        .map(|name| Field::new(name.into(), schema.get(name).cloned().unwrap_or_default()))
+        .collect::<Schema>();
+    let moved = columns.resolve(&current, "ReorderCols")?;
+    if let Some(duplicate) = moved.iter().enumerate().find_map(|(index, name)| moved[..index].contains(name).then_some(name)) {
+        return Err(DiasError::invalid_parameter("ReorderCols", "columns", format!("column `{duplicate}` is listed more than once")));
+    }
+    Ok(moved)
+}
+
+impl Move {
+    fn apply(&self, order: Vec<String>, schema: &Schema) -> DiasResult<Vec<String>> {
+        let order = match self {
+            Move::Front(columns) | Move::Back(columns) => {
+                let moved = resolve(columns, &order, schema)?;
+                let rest = order.into_iter().filter(|name| !moved.contains(name));
+                if matches!(self, Move::Front(_)) {
+                    moved.iter().cloned().chain(rest).collect()
+                } else {
+                    rest.chain(moved.iter().cloned()).collect()
+                }
+            },
+            Move::Before { columns, anchor } | Move::After { columns, anchor } => {
+                if !schema.contains(anchor) {
+                    return Err(DiasError::invalid_parameter("ReorderCols", "anchor", format!("column `{anchor}` does not exist")));
+                }
+                let moved = resolve(columns, &order, schema)?;
+                if moved.contains(anchor) {
+                    return Err(DiasError::invalid_parameter("ReorderCols", "anchor", format!("column `{anchor}` cannot be moved relative to itself")));
+                }
+                let mut rest = order.into_iter().filter(|name| !moved.contains(name)).collect::<Vec<_>>();
+                let mut position = rest.iter().position(|name| name == anchor).unwrap_or_default();
+                if matches!(self, Move::After { .. }) {
+                    position += 1;
+                }
+                rest.splice(position..position, moved);
+                rest
+            },
+            Move::Alphabetical { descending } => {
+                let mut order = order;
+                order.sort();
+                if *descending {
+                    order.reverse();
+                }
+                order
+            }
+        };
+        Ok(order)
+    }
+}
+
+impl Transformation for ReorderCols {
+    fn apply(&self, mut df: LazyFrame) -> DiasResult<LazyFrame> {
+        let schema = df.collect_schema()?;
+        let order = self.moves.iter().try_fold(
+            schema.iter_names().map(|name| name.to_string()).collect::<Vec<_>>(),
+            |order, column_move| column_move.apply(order, &schema)
+        )?;
+        Ok(df.select([cols(order)]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(transformation: &ReorderCols) -> DiasResult<Vec<String>> {
+        let df = df!(
+            "id" => &[1],
+            "name" => &["Alice"],
+            "metric_b" => &[2.0],
+            "metric_a" => &[1.0],
+            "total" => &[3.0]
+        ).unwrap();
+
+        let result = transformation.apply(df.lazy())?.collect()?;
+        Ok(result.get_column_names().iter().map(|name| name.to_string()).collect())
+    }
+
+    #[test]
+    fn test_can_move_to_front_and_back() {
+        let transformation = ReorderCols {
+            moves: vec![
+                Move::Front(Selector::names(["total"])),
+                Move::Back(Selector::names(["name", "id"])),
+            ]
+        };
+
+        assert_eq!(names(&transformation).unwrap(), ["total", "metric_b", "metric_a", "name", "id"]);
+    }
+
+    #[test]
+    fn test_can_place_relative_to_anchor() {
+        let transformation = ReorderCols {
+            moves: vec![
+                Move::After { columns: Selector::names(["total"]), anchor: String::from("id") },
+                Move::Before { columns: Selector::Regex(String::from("^metric_")), anchor: String::from("id") },
+            ]
+        };
+
+        assert_eq!(names(&transformation).unwrap(), ["metric_b", "metric_a", "id", "total", "name"]);
+    }
+
+    #[test]
+    fn test_can_sort_alphabetically() {
+        let ascending = ReorderCols { moves: vec![Move::Alphabetical { descending: false }] };
+        let descending = ReorderCols { moves: vec![Move::Alphabetical { descending: true }] };
+
+        assert_eq!(names(&ascending).unwrap(), ["id", "metric_a", "metric_b", "name", "total"]);
+        assert_eq!(names(&descending).unwrap(), ["total", "name", "metric_b", "metric_a", "id"]);
+    }
+
+    #[test]
+    fn test_unknown_columns_are_rejected() {
+        let missing = ReorderCols { moves: vec![Move::Front(Selector::names(["missing"]))] };
+        let anchor = ReorderCols {
+            moves: vec![Move::Before { columns: Selector::names(["id"]), anchor: String::from("missing") }]
+        };
+        let itself = ReorderCols {
+            moves: vec![Move::After { columns: Selector::names(["id", "name"]), anchor: String::from("id") }]
+        };
+
+        assert!(matches!(names(&missing), Err(DiasError::InvalidParameter { step: "ReorderCols", parameter: "columns", .. })));
+        assert!(matches!(names(&anchor), Err(DiasError::InvalidParameter { step: "ReorderCols", parameter: "anchor", .. })));
+        assert!(matches!(names(&itself), Err(DiasError::InvalidParameter { step: "ReorderCols", parameter: "anchor", .. })));
+    }
+}
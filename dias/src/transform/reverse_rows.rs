@@ -1,13 +1,16 @@
 use polars::prelude::LazyFrame;
+use serde::{Deserialize, Serialize};
 
+use crate::error::DiasResult;
 use super::transformer::Transformation;
 
 
+#[derive(Serialize, Deserialize)]
 pub struct ReverseRows {}
 
 impl Transformation for ReverseRows {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
-        df.reverse()
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame> {
+        Ok(df.reverse())
     }
 }
 
@@ -24,7 +27,7 @@ mod tests {
             "col1" => &[1, 2, 3, 4, 5],
         ).unwrap().lazy();
 
-        let result = transformation.apply(df.lazy()).collect().unwrap();
+        let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
         let expected = df!(
             "col1" => &[5, 4, 3, 2, 1]
         ).unwrap();
@@ -1,14 +1,25 @@
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::error::DiasResult;
+use super::selector::Selector;
 use super::transformer::Transformation;
 
+#[derive(Serialize, Deserialize)]
 pub struct SelectCols {
-    columns: Vec<String>
+    columns: Selector
+}
+
+impl SelectCols {
+    pub fn new(columns: impl Into<Selector>) -> SelectCols {
+        SelectCols { columns: columns.into() }
+    }
 }
 
 impl Transformation for SelectCols {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
-        df.select(&[cols(self.columns)])
+    fn apply(&self, mut df: LazyFrame) -> DiasResult<LazyFrame> {
+        let columns = self.columns.resolve(&*df.collect_schema()?, "SelectCols")?;
+        Ok(df.select([cols(columns)]))
     }
 }
 
@@ -16,12 +27,11 @@ impl Transformation for SelectCols {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::string::String;
 
     #[test]
     fn test_can_select_cols_subset(){
         let transformation = SelectCols {
-            columns: vec![String::from("col2"), String::from("col4")]
+            columns: Selector::names(["col2", "col4"])
         };
 
         let df = df!(
@@ -31,7 +41,7 @@ mod tests {
             "col4" => &[4],
         ).ok().unwrap().lazy();
 
-        let result = transformation.apply(df).collect().unwrap().schema();
+        let result = transformation.apply(df).unwrap().collect().unwrap().schema();
 
         assert_eq!(result.len(), 2);
     }
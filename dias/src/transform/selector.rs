@@ -0,0 +1,193 @@
+use polars::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DiasError, DiasResult};
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum DtypeGroup {
+    Numeric,
+    String,
+    // Dates, datetimes, durations and times
+    Temporal,
+}
+
+impl DtypeGroup {
+    fn matches(&self, dtype: &DataType) -> bool {
+        match self {
+            DtypeGroup::Numeric => dtype.is_numeric(),
+            DtypeGroup::String => matches!(dtype, DataType::String),
+            DtypeGroup::Temporal => dtype.is_temporal()
+        }
+    }
+}
+
+// Resolved against the schema at run time, so columns added upstream are picked up
+// by patterns and dtypes without touching the pipeline
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Selector {
+    Regex(String),
+    Dtype(DtypeGroup),
+    // Zero based and end exclusive, a missing end runs to the last column
+    Range { start: usize, end: Option<usize> },
+    Union(Vec<Selector>),
+    // Columns picked by `include` but not by `exclude`
+    Difference { include: Box<Selector>, exclude: Box<Selector> },
+    // A plain list of names, kept untagged so existing definitions still load
+    #[serde(untagged)]
+    Names(Vec<String>),
+}
+
+impl From<Vec<String>> for Selector {
+    fn from(names: Vec<String>) -> Self {
+        Selector::Names(names)
+    }
+}
+
+impl Selector {
+    pub fn names<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Selector {
+        Selector::Names(names.into_iter().map(Into::into).collect())
+    }
+
+    pub fn union(selectors: impl IntoIterator<Item = Selector>) -> Selector {
+        Selector::Union(selectors.into_iter().collect())
+    }
+
+    pub fn difference(include: Selector, exclude: Selector) -> Selector {
+        Selector::Difference { include: Box::new(include), exclude: Box::new(exclude) }
+    }
+
+    // One flag per schema column, `step` names the step in error messages and unknown
+    // names are only an error when `strict` is set
+    fn mask(&self, schema: &Schema, step: &'static str, strict: bool) -> DiasResult<Vec<bool>> {
+        let mask = match self {
+            Selector::Names(names) => {
+                if let Some(missing) = names.iter().find(|name| strict && !schema.contains(name)) {
+                    return Err(DiasError::invalid_parameter(step, "columns", format!("column `{missing}` does not exist")));
+                }
+                schema.iter_names().map(|name| names.iter().any(|other| other == name.as_str())).collect()
+            },
+            Selector::Regex(pattern) => {
+                let regex = Regex::new(pattern)
+                    .map_err(|err| DiasError::invalid_parameter(step, "columns", err.to_string()))?;
+                schema.iter_names().map(|name| regex.is_match(name)).collect()
+            },
+            Selector::Dtype(group) => schema.iter_names_and_dtypes().map(|(_, dtype)| group.matches(dtype)).collect(),
+            Selector::Range { start, end } => {
+                if let Some(end) = end.filter(|end| end < start) {
+                    return Err(DiasError::invalid_parameter(step, "columns", format!("range starts at {start} but ends at {end}")));
+                }
+                // A range past the last column selects nothing
+                let end = end.unwrap_or(schema.len()).min(schema.len());
+                (0..schema.len()).map(|index| (*start..end).contains(&index)).collect()
+            },
+            Selector::Union(selectors) => {
+                let mut mask = vec![false; schema.len()];
+                for selector in selectors {
+                    for (selected, other) in mask.iter_mut().zip(selector.mask(schema, step, strict)?) {
+                        *selected |= other;
+                    }
+                }
+                mask
+            },
+            Selector::Difference { include, exclude } => include.mask(schema, step, strict)?
+                .into_iter()
+                .zip(exclude.mask(schema, step, strict)?)
+                .map(|(include, exclude)| include && !exclude)
+                .collect()
+        };
+        Ok(mask)
+    }
+
+    // Plain names keep the order they were given in, every other selector follows the schema
+    pub fn resolve(&self, schema: &Schema, step: &'static str) -> DiasResult<Vec<String>> {
+        self.resolve_with(schema, step, true)
+    }
+
+    // Same as `resolve` but names missing from the schema are skipped
+    pub fn resolve_present(&self, schema: &Schema, step: &'static str) -> DiasResult<Vec<String>> {
+        self.resolve_with(schema, step, false)
+    }
+
+    fn resolve_with(&self, schema: &Schema, step: &'static str, strict: bool) -> DiasResult<Vec<String>> {
+        if let Selector::Names(names) = self {
+            self.mask(schema, step, strict)?;
+            return Ok(names.iter().filter(|name| schema.contains(name)).cloned().collect());
+        }
+        let names = schema
+            .iter_names()
+            .zip(self.mask(schema, step, strict)?)
+            .filter(|(_, selected)| *selected)
+            .map(|(name, _)| name.to_string())
+            .collect();
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn make_schema() -> Schema {
+        df!(
+            "id" => &[1],
+            "name" => &["Alice"],
+            "metric_2026_q2" => &[1.5],
+            "metric_2026_q3" => &[2.5],
+            "ordered_on" => &[NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()]
+        ).unwrap().schema()
+    }
+
+    #[test]
+    fn test_can_resolve_selectors() {
+        let schema = make_schema();
+
+        let regex = Selector::Regex(String::from("^metric_"));
+        let numeric = Selector::Dtype(DtypeGroup::Numeric);
+        let range = Selector::Range { start: 3, end: None };
+        let union = Selector::union([Selector::names(["name"]), Selector::Dtype(DtypeGroup::Temporal)]);
+        let difference = Selector::difference(numeric.clone(), regex.clone());
+
+        assert_eq!(regex.resolve(&schema, "SelectCols").unwrap(), ["metric_2026_q2", "metric_2026_q3"]);
+        assert_eq!(numeric.resolve(&schema, "SelectCols").unwrap(), ["id", "metric_2026_q2", "metric_2026_q3"]);
+        assert_eq!(range.resolve(&schema, "SelectCols").unwrap(), ["metric_2026_q3", "ordered_on"]);
+        assert_eq!(union.resolve(&schema, "SelectCols").unwrap(), ["name", "ordered_on"]);
+        assert_eq!(difference.resolve(&schema, "SelectCols").unwrap(), ["id"]);
+    }
+
+    #[test]
+    fn test_names_stay_untagged() {
+        let selector: Selector = serde_json::from_str(r#"["id", "name"]"#).unwrap();
+        let nested: Selector = serde_json::from_str(r#"{"Union": [["id"], {"Regex": "q3$"}]}"#).unwrap();
+
+        assert_eq!(selector.resolve(&make_schema(), "SelectCols").unwrap(), ["id", "name"]);
+        assert_eq!(Selector::names(["name", "id"]).resolve(&make_schema(), "SelectCols").unwrap(), ["name", "id"]);
+        assert_eq!(nested.resolve(&make_schema(), "SelectCols").unwrap(), ["id", "metric_2026_q3"]);
+        assert_eq!(serde_json::to_string(&selector).unwrap(), r#"["id","name"]"#);
+    }
+
+    #[test]
+    fn test_invalid_selectors_are_rejected() {
+        let schema = make_schema();
+
+        let missing = Selector::names(["missing"]).resolve(&schema, "SelectCols");
+        let regex = Selector::Regex(String::from("(")).resolve(&schema, "SelectCols");
+        let range = Selector::Range { start: 4, end: Some(2) }.resolve(&schema, "SelectCols");
+
+        assert!(matches!(missing, Err(DiasError::InvalidParameter { step: "SelectCols", parameter: "columns", .. })));
+        assert!(matches!(regex, Err(DiasError::InvalidParameter { step: "SelectCols", parameter: "columns", .. })));
+        assert!(matches!(range, Err(DiasError::InvalidParameter { step: "SelectCols", parameter: "columns", .. })));
+    }
+
+    #[test]
+    fn test_lenient_resolution_skips_unknown_names() {
+        let schema = make_schema();
+
+        let names = Selector::names(["missing", "name", "id"]).resolve_present(&schema, "ExcludeCols").unwrap();
+        let range = Selector::Range { start: 10, end: None }.resolve(&schema, "SelectCols").unwrap();
+
+        assert_eq!(names, ["name", "id"]);
+        assert!(range.is_empty());
+    }
+}
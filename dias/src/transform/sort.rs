@@ -1,6 +1,10 @@
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::error::DiasResult;
 use super::transformer::Transformation;
 
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Sort {
     pub by: Vec<String>,
     pub descending: Option<bool>,
@@ -12,6 +16,7 @@ pub struct Sort {
 }
 
 impl Sort {
+    #[allow(clippy::clone_on_copy)]
     fn make_sort_options(&self) -> SortMultipleOptions {
        let mut sort_options = SortMultipleOptions::default();
 
@@ -52,9 +57,9 @@ impl Default for Sort {
 }
 
 impl Transformation for Sort {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame> {
        let sort_options = self.make_sort_options();
-       df.sort(self.by, sort_options)
+       Ok(df.sort(self.by.clone(), sort_options))
     }
 }
 
@@ -74,7 +79,7 @@ mod tests {
             nulls_last: Some(true),
             ..Default::default()
        };
-       let result = transformation.apply(df.lazy()).collect().unwrap();
+       let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
        let expected = df! {
            "a" => [Some(1), Some(2), Some(4), None, None],
            "b" => [Some(5), None, Some(2), Some(3), Some(1)]
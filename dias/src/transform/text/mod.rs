@@ -1 +1 @@
-mod parse;
+pub mod parse;
@@ -1,8 +1,11 @@
 use std::rc::Rc;
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::error::DiasResult;
 use crate::transform::transformer::Transformation;
 
-struct ParseText {
+#[derive(Serialize, Deserialize)]
+pub struct ParseText {
     cols: Vec<Rc<ParseTextOp>>
 }
 
@@ -15,7 +18,7 @@ impl ParseText {
         names.dedup();
         names
     }
-    fn get_exprs(self) -> Vec<Expr> {
+    fn get_exprs(&self) -> Vec<Expr> {
         self.cols
             .iter()
             .map(|target| target.to_expr())
@@ -24,17 +27,18 @@ impl ParseText {
 }
 
 impl Transformation for ParseText {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
-        df.select(
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame> {
+        Ok(df.select(
             itertools::concat([
                 vec![all().exclude(self.get_col_names())],
                 self.get_exprs()
             ])
-        )
+        ))
     }
 }
 
-enum ParseTextOp {
+#[derive(Serialize, Deserialize)]
+pub enum ParseTextOp {
     ToDate{column: String, options: StrptimeOptions, alias: String},
     ToTime{column: String, options: StrptimeOptions, alias: String},
     ToDateTime{
@@ -54,7 +58,8 @@ impl ParseTextOp {
             Self::ToDateTime { column, .. } => String::from(column)
         }
     }
-    fn to_expr(&self) -> Expr {
+    #[allow(clippy::clone_on_copy)]
+    pub(crate) fn to_expr(&self) -> Expr {
         match &self {
             ParseTextOp::ToDate { column, options, alias } => {
                 col(column).str().to_date(options.clone()).alias(alias)
@@ -119,7 +124,7 @@ mod tests {
                 "2020-03-03 16:50:03"
             ]
         ).unwrap();
-        let result = transformation.apply(df.lazy()).collect().unwrap();
+        let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
         let expected = df!(
             "Date" => &[
                 NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
@@ -151,7 +156,7 @@ mod tests {
                 "2020-03-03 16:53:03"
             ]
         ).unwrap();
-        let result = transformation.apply(df.lazy()).collect().unwrap();
+        let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
         let expected = df!(
             "Time" => &[
                 NaiveTime::from_hms_opt(16, 51, 1).unwrap(),
@@ -185,7 +190,7 @@ mod tests {
                 "2020-03-03 16:53:03"
             ]
         ).unwrap();
-        let result = transformation.apply(df.lazy()).collect().unwrap();
+        let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
         let expected = df!(
             "DateTime" => &[
                 NaiveDate::from_ymd_opt(2020, 1, 1).unwrap().and_hms_opt(16, 51, 1).unwrap(),
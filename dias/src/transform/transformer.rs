@@ -1,6 +1,7 @@
 use polars::prelude::*;
 
+use crate::error::DiasResult;
+
 pub trait Transformation {
-    fn apply(self, df: LazyFrame) -> LazyFrame;
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame>;
 }
-
@@ -1,13 +1,16 @@
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::error::DiasResult;
 use super::transformer::Transformation;
 
 // Note: This a very expensive operation
-struct Transpose {}
+#[derive(Serialize, Deserialize)]
+pub struct Transpose {}
 
 impl Transformation for Transpose {
-    fn apply(self, df: LazyFrame) -> LazyFrame {
-        df.collect().unwrap().transpose(None, None).unwrap().lazy()
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame> {
+        Ok(df.collect()?.transpose(None, None)?.lazy())
     }
 }
 
@@ -23,7 +26,7 @@ mod tests {
             "b" => &["b1", "b2", "b3", "b4"]
         ).unwrap();
 
-        let result = transformation.apply(df.lazy()).collect().unwrap();
+        let result = transformation.apply(df.lazy()).unwrap().collect().unwrap();
         let expected = df!(
             "collumn_0" => &["a1", "b1"],
             "collumn_1" => &["a2", "b2"],
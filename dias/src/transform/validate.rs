@@ -0,0 +1,349 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DiasError, DiasResult, RuleReport, ValidationReport};
+use crate::io::write::writer::Writer;
+use crate::pipeline::definition::SinkDefinition;
+use crate::transform::filter::clause::{Clause, FilterOperators};
+use crate::transform::transformer::Transformation;
+
+// Column added to rejected rows, listing the rules they broke
+pub const FAILED_RULES: &str = "failed_rules";
+
+#[derive(Serialize, Deserialize)]
+pub enum Rule {
+    NotNull { column: String },
+    // Rows whose combination of values appears more than once
+    Unique { columns: Vec<String> },
+    // Both bounds are inclusive
+    Range { column: String, min: Option<Expr>, max: Option<Expr> },
+    Regex { column: String, pattern: String },
+    AllowedSet { column: String, values: Expr },
+    // Every value must exist in `other_column` of the `other` frame
+    References {
+        column: String,
+        #[serde(with = "crate::transform::optional_frame")]
+        other: Option<LazyFrame>,
+        other_column: String,
+    },
+    // Rows must satisfy the clause
+    Check { name: String, clause: Clause },
+}
+
+impl Rule {
+    pub fn name(&self) -> String {
+        match self {
+            Rule::NotNull { column } => format!("not_null({column})"),
+            Rule::Unique { columns } => format!("unique({})", columns.join(", ")),
+            Rule::Range { column, .. } => format!("range({column})"),
+            Rule::Regex { column, .. } => format!("regex({column})"),
+            Rule::AllowedSet { column, .. } => format!("allowed_set({column})"),
+            Rule::References { column, other_column, .. } => format!("references({column} -> {other_column})"),
+            Rule::Check { name, .. } => name.clone()
+        }
+    }
+
+    fn clause(column: &str, operator: FilterOperators) -> DiasResult<Expr> {
+        Clause { column: column.to_string(), operator }.make_expr()
+    }
+
+    // Missing values only break the not-null rule, the other rules let them through
+    fn make_expr(&self) -> DiasResult<Expr> {
+        let expr = match self {
+            Rule::NotNull { column } => {
+                return Rule::clause(column, FilterOperators::PolarsBooleanFunction(BooleanFunction::IsNotNull));
+            },
+            Rule::Unique { columns } => {
+                if columns.is_empty() {
+                    return Err(DiasError::invalid_parameter("Validate", "columns", "unique rule needs at least one column"));
+                }
+                let columns = columns.iter().map(col).collect::<Vec<_>>();
+                let has_null = any_horizontal(columns.iter().map(|column| column.clone().is_null()).collect::<Vec<_>>())?;
+                return Ok(len().over(columns).eq(lit(1)).or(has_null));
+            },
+            Rule::Range { column, min, max } => {
+                let bounds = [(Operator::GtEq, min), (Operator::LtEq, max)]
+                    .into_iter()
+                    .filter_map(|(op, bound)| bound.clone().map(|bound| (op, bound)))
+                    .map(|(op, bound)| Rule::clause(column, FilterOperators::PolarsOperator(op, bound)))
+                    .collect::<DiasResult<Vec<_>>>()?;
+                bounds
+                    .into_iter()
+                    .reduce(|expr, bound| expr.and(bound))
+                    .ok_or_else(|| DiasError::invalid_parameter("Validate", "range", "range rule needs a min or a max"))?
+            },
+            Rule::Regex { column, pattern } => Rule::clause(column, FilterOperators::Contains {
+                pattern: pattern.clone(),
+                literal: false,
+                strict: true
+            })?,
+            Rule::AllowedSet { column, values } => Rule::clause(column, FilterOperators::IsIn(values.clone()))?,
+            Rule::References { column, .. } => col(column).is_null().or(col(REFERENCE).is_not_null()),
+            Rule::Check { clause, .. } => clause.make_expr()?
+        };
+        Ok(expr.fill_null(lit(true)))
+    }
+}
+
+const REFERENCE: &str = "__dias_reference";
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ValidationMode {
+    #[default]
+    Fail,
+    Drop,
+    // Offending rows are kept aside with a `failed_rules` column, written to the `rejects`
+    // sink or left in the outcome
+    Route,
+}
+
+#[derive(Default)]
+pub struct ValidationOutcome {
+    pub report: Option<ValidationReport>,
+    pub rejects: Option<DataFrame>,
+}
+
+// Validation needs the data, so the frame is collected when the step is applied
+#[derive(Serialize, Deserialize)]
+pub struct Validate {
+    pub rules: Vec<Rule>,
+    #[serde(default)]
+    pub mode: ValidationMode,
+    // Where route mode writes the rejected rows
+    #[serde(default)]
+    pub rejects: Option<SinkDefinition>,
+    // Filled by every run, clone it before handing the step over to a pipeline
+    #[serde(skip)]
+    pub outcome: Rc<RefCell<ValidationOutcome>>,
+}
+
+impl Validate {
+    pub fn new(rules: Vec<Rule>, mode: ValidationMode) -> Validate {
+        Validate { rules, mode, rejects: None, outcome: Default::default() }
+    }
+
+    fn flag(index: usize) -> String {
+        format!("__dias_rule_{index}")
+    }
+
+    fn with_flags(&self, mut df: LazyFrame) -> DiasResult<LazyFrame> {
+        let mut flags = Vec::with_capacity(self.rules.len());
+        for (index, rule) in self.rules.iter().enumerate() {
+            if let Rule::References { column, other, other_column } = rule {
+                let Some(other) = other else {
+                    return Err(DiasError::invalid_parameter("Validate", "other", "expected a frame to check references against"));
+                };
+                let keys = other.clone()
+                    .select([col(other_column), lit(true).alias(REFERENCE)])
+                    .unique(None, UniqueKeepStrategy::Any);
+                df = df
+                    .join(keys, [col(column)], [col(other_column)], JoinArgs::new(JoinType::Left))
+                    .with_column(rule.make_expr()?.alias(Validate::flag(index)))
+                    .drop([REFERENCE]);
+                continue;
+            }
+            flags.push(rule.make_expr()?.alias(Validate::flag(index)));
+        }
+        Ok(df.with_columns(flags))
+    }
+
+    // Splits the frame into valid and rejected rows
+    pub fn validate(&self, df: LazyFrame) -> DiasResult<(DataFrame, DataFrame, ValidationReport)> {
+        if self.rules.is_empty() {
+            return Err(DiasError::invalid_parameter("Validate", "rules", "no rules to validate"));
+        }
+        let flags = (0..self.rules.len()).map(Validate::flag).collect::<Vec<_>>();
+        let passed = all_horizontal(flags.iter().map(col).collect::<Vec<_>>())?;
+        let flagged = self.with_flags(df)?.collect()?;
+
+        let rules = self.rules
+            .iter()
+            .zip(&flags)
+            .map(|(rule, flag)| {
+                let column = flagged.column(flag)?.bool()?;
+                Ok(RuleReport { rule: rule.name(), violations: column.len() - column.sum().unwrap_or(0) as usize })
+            })
+            .collect::<DiasResult<Vec<_>>>()?;
+
+        let valid = flagged.clone().lazy()
+            .filter(passed.clone())
+            .drop(flags.clone())
+            .collect()?;
+        // Names of the broken rules, the ones a row passed are null and skipped
+        let failed_rules = self.rules
+            .iter()
+            .zip(&flags)
+            .map(|(rule, flag)| when(col(flag).not()).then(lit(rule.name())).otherwise(lit(Null {})))
+            .collect::<Vec<_>>();
+        let rejects = flagged.lazy()
+            .filter(passed.not())
+            .with_column(concat_str(failed_rules, ", ", true).alias(FAILED_RULES))
+            .drop(flags)
+            .collect()?;
+
+        let report = ValidationReport {
+            rows: valid.height() + rejects.height(),
+            rejected_rows: rejects.height(),
+            rules
+        };
+        Ok((valid, rejects, report))
+    }
+}
+
+impl Transformation for Validate {
+    fn apply(&self, df: LazyFrame) -> DiasResult<LazyFrame> {
+        // A step loaded from a definition has no handle on the outcome, its rejects would be lost
+        if self.mode == ValidationMode::Route && self.rejects.is_none() && Rc::strong_count(&self.outcome) == 1 {
+            return Err(DiasError::invalid_parameter("Validate", "rejects", "route mode needs a sink for the rejected rows"));
+        }
+        let (valid, mut rejects, report) = self.validate(df)?;
+        let mut outcome = self.outcome.borrow_mut();
+        outcome.report = Some(report.clone());
+        outcome.rejects = None;
+
+        match self.mode {
+            ValidationMode::Fail if !report.is_valid() => Err(DiasError::Validation(report)),
+            ValidationMode::Route => {
+                if let Some(sink) = &self.rejects {
+                    sink.clone().load(&mut rejects)?;
+                }
+                outcome.rejects = Some(rejects);
+                Ok(valid.lazy())
+            },
+            _ => Ok(valid.lazy())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::write::csv::CsvSink;
+
+    fn make_df() -> DataFrame {
+        df!(
+            "order_id" => &[1, 2, 2, 4, 5],
+            "customer_id" => &[Some(1), Some(2), Some(9), None, Some(1)],
+            "email" => &["a@x.com", "bob", "c@x.com", "d@x.com", "e@x.com"],
+            "amount" => &[100, -5, 300, 400, 500],
+            "country" => &["FR", "MA", "FR", "US", "MA"]
+        ).unwrap()
+    }
+
+    fn make_rules() -> Vec<Rule> {
+        vec![
+            Rule::NotNull { column: String::from("customer_id") },
+            Rule::Unique { columns: vec![String::from("order_id")] },
+            Rule::Range { column: String::from("amount"), min: Some(lit(0)), max: Some(lit(450)) },
+            Rule::Regex { column: String::from("email"), pattern: String::from("^[^@]+@[^@]+$") },
+            Rule::AllowedSet { column: String::from("country"), values: lit(Series::new("".into(), ["FR", "MA"])) },
+            Rule::References {
+                column: String::from("customer_id"),
+                other: Some(df!("id" => &[1, 2, 3]).unwrap().lazy()),
+                other_column: String::from("id")
+            },
+        ]
+    }
+
+    #[test]
+    fn test_can_report_violations_per_rule() {
+        let transformation = Validate::new(make_rules(), ValidationMode::Fail);
+
+        let (_, _, report) = transformation.validate(make_df().lazy()).unwrap();
+        let violations = report.rules
+            .iter()
+            .map(|rule| (rule.rule.as_str(), rule.violations))
+            .collect::<Vec<_>>();
+
+        assert_eq!(report.rejected_rows, 4);
+        assert_eq!(violations, [
+            ("not_null(customer_id)", 1),
+            ("unique(order_id)", 2),
+            ("range(amount)", 2),
+            ("regex(email)", 1),
+            ("allowed_set(country)", 1),
+            ("references(customer_id -> id)", 1),
+        ]);
+    }
+
+    #[test]
+    fn test_fail_mode_returns_report() {
+        let transformation = Validate::new(make_rules(), ValidationMode::Fail);
+
+        let result = transformation.apply(make_df().lazy());
+
+        assert!(matches!(result, Err(DiasError::Validation(report)) if report.rejected_rows == 4));
+    }
+
+    #[test]
+    fn test_drop_mode_keeps_valid_rows() {
+        let transformation = Validate::new(vec![
+            Rule::Range { column: String::from("amount"), min: Some(lit(0)), max: None },
+            Rule::Check {
+                name: String::from("not_us"),
+                clause: Clause {
+                    column: String::from("country"),
+                    operator: FilterOperators::PolarsOperator(Operator::NotEq, lit("US"))
+                }
+            },
+        ], ValidationMode::Drop);
+
+        let result = transformation.apply(make_df().lazy()).unwrap().collect().unwrap();
+
+        assert_eq!(result.column("order_id").unwrap().i32().unwrap().to_vec(), [Some(1), Some(2), Some(5)]);
+        assert_eq!(transformation.outcome.borrow().report.as_ref().unwrap().rejected_rows, 2);
+    }
+
+    #[test]
+    fn test_route_mode_keeps_rejects() {
+        let rules = make_rules().into_iter().take(3).collect();
+        let transformation = Validate::new(rules, ValidationMode::Route);
+        let outcome = transformation.outcome.clone();
+
+        let result = transformation.apply(make_df().lazy()).unwrap().collect().unwrap();
+        let outcome = outcome.borrow();
+        let rejects = outcome.rejects.as_ref().unwrap();
+
+        assert_eq!(result.column("order_id").unwrap().i32().unwrap().to_vec(), [Some(1)]);
+        assert_eq!(
+            rejects.column(FAILED_RULES).unwrap().str().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+            ["unique(order_id), range(amount)", "unique(order_id)", "not_null(customer_id)", "range(amount)"]
+        );
+    }
+
+    #[test]
+    fn test_null_keys_are_not_duplicates() {
+        let df = df!(
+            "key" => &[Some(1), None, None, Some(1), Some(2)]
+        ).unwrap();
+        let transformation = Validate::new(vec![Rule::Unique { columns: vec![String::from("key")] }], ValidationMode::Fail);
+
+        let (valid, _, report) = transformation.validate(df.lazy()).unwrap();
+
+        assert_eq!(report.rules[0].violations, 2);
+        assert_eq!(valid.column("key").unwrap().i32().unwrap().to_vec(), [None, None, Some(2)]);
+    }
+
+    #[test]
+    fn test_route_mode_writes_rejects_to_sink() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("rejects.csv");
+        let rules = make_rules().into_iter().skip(2).take(1).collect();
+        let transformation = Validate {
+            rejects: Some(SinkDefinition::Csv(CsvSink::new(&path))),
+            ..Validate::new(rules, ValidationMode::Route)
+        };
+
+        transformation.apply(make_df().lazy()).unwrap().collect().unwrap();
+        let unrouted = Validate::new(make_rules(), ValidationMode::Route).apply(make_df().lazy());
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "order_id,customer_id,email,amount,country,failed_rules\n2,2,bob,-5,MA,range(amount)\n5,1,e@x.com,500,MA,range(amount)\n"
+        );
+        assert!(matches!(unrouted, Err(DiasError::InvalidParameter { step: "Validate", parameter: "rejects", .. })));
+    }
+}
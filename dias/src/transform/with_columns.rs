@@ -0,0 +1,281 @@
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DiasError, DiasResult};
+use crate::transform::filter::clause::{date_part, length, DatePart};
+use crate::transform::filter::condition::Condition;
+use crate::transform::transformer::Transformation;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Function {
+    Abs,
+    Round(u32),
+    Floor,
+    Ceil,
+    Lower,
+    Upper,
+    Trim,
+    Len,
+    Year,
+    Month,
+    Day,
+    // 1 (Monday) to 7 (Sunday)
+    Weekday,
+    Cast(DataType),
+}
+
+impl Function {
+    fn apply(&self, expr: Expr) -> Expr {
+        match self {
+            Function::Abs => expr.abs(),
+            Function::Round(decimals) => expr.round(*decimals),
+            Function::Floor => expr.floor(),
+            Function::Ceil => expr.ceil(),
+            Function::Lower => expr.str().to_lowercase(),
+            Function::Upper => expr.str().to_uppercase(),
+            Function::Trim => expr.str().strip_chars(lit(Null {})),
+            Function::Len => length(expr),
+            Function::Year => date_part(expr, DatePart::Year),
+            Function::Month => date_part(expr, DatePart::Month),
+            Function::Day => date_part(expr, DatePart::Day),
+            Function::Weekday => date_part(expr, DatePart::Weekday),
+            Function::Cast(dtype) => expr.strict_cast(dtype.clone())
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WhenThen {
+    pub condition: Condition,
+    pub then: Computation,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum Computation {
+    Column(String),
+    Literal(LiteralValue),
+    Arithmetic { left: Box<Computation>, operator: Operator, right: Box<Computation> },
+    // Missing parts are skipped rather than nulling the whole value
+    Concat { parts: Vec<Computation>, separator: String },
+    // The first branch whose condition holds wins
+    When { branches: Vec<WhenThen>, otherwise: Box<Computation> },
+    Call { function: Function, argument: Box<Computation> },
+    Coalesce(Vec<Computation>),
+    // Escape hatch for anything polars can express
+    Expr(Expr),
+}
+
+impl Computation {
+    pub fn column(name: impl Into<String>) -> Computation {
+        Computation::Column(name.into())
+    }
+
+    pub fn arithmetic(left: Computation, operator: Operator, right: Computation) -> Computation {
+        Computation::Arithmetic { left: Box::new(left), operator, right: Box::new(right) }
+    }
+
+    pub fn call(function: Function, argument: Computation) -> Computation {
+        Computation::Call { function, argument: Box::new(argument) }
+    }
+
+    fn make_all(computations: &[Computation]) -> DiasResult<Vec<Expr>> {
+        computations.iter().map(Computation::make_expr).collect()
+    }
+
+    pub fn make_expr(&self) -> DiasResult<Expr> {
+        let expr = match self {
+            Computation::Column(name) => col(name),
+            Computation::Literal(value) => lit(value.clone()),
+            Computation::Arithmetic { left, operator, right } => {
+                if !operator.is_arithmetic() {
+                    return Err(DiasError::invalid_parameter("WithColumns", "operator", format!("expected an arithmetic operator, found `{operator}`")));
+                }
+                binary_expr(left.make_expr()?, *operator, right.make_expr()?)
+            },
+            Computation::Concat { parts, separator } => {
+                if parts.is_empty() {
+                    return Err(DiasError::invalid_parameter("WithColumns", "parts", "nothing to concatenate"));
+                }
+                let parts = Computation::make_all(parts)?
+                    .into_iter()
+                    .map(|part| part.cast(DataType::String))
+                    .collect::<Vec<_>>();
+                concat_str(parts, separator, true)
+            },
+            Computation::When { branches, otherwise } => {
+                let mut branches = branches.iter().rev();
+                let Some(last) = branches.next() else {
+                    return Err(DiasError::invalid_parameter("WithColumns", "branches", "expected at least one when/then branch"));
+                };
+                // Built from the last branch outwards so the first one is checked first
+                let mut expr = when(last.condition.make_expr()?)
+                    .then(last.then.make_expr()?)
+                    .otherwise(otherwise.make_expr()?);
+                for branch in branches {
+                    expr = when(branch.condition.make_expr()?)
+                        .then(branch.then.make_expr()?)
+                        .otherwise(expr);
+                }
+                expr
+            },
+            Computation::Call { function, argument } => function.apply(argument.make_expr()?),
+            Computation::Coalesce(computations) => {
+                if computations.is_empty() {
+                    return Err(DiasError::invalid_parameter("WithColumns", "coalesce", "expected at least one value"));
+                }
+                coalesce(&Computation::make_all(computations)?)
+            },
+            Computation::Expr(expr) => expr.clone()
+        };
+        Ok(expr)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct NewColumn {
+    pub name: String,
+    pub computation: Computation,
+}
+
+// Columns are added in order, so a column can use the ones defined before it
+#[derive(Serialize, Deserialize)]
+pub struct WithColumns {
+    pub columns: Vec<NewColumn>,
+    // Replacing an existing column is an error unless enabled
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+impl Transformation for WithColumns {
+    fn apply(&self, mut df: LazyFrame) -> DiasResult<LazyFrame> {
+        let mut names = df.collect_schema()?
+            .iter_names()
+            .map(|name| name.to_string())
+            .collect::<PlHashSet<_>>();
+
+        for column in &self.columns {
+            if !names.insert(column.name.clone()) && !self.overwrite {
+                return Err(DiasError::invalid_parameter(
+                    "WithColumns",
+                    "columns",
+                    format!("column `{}` already exists, enable `overwrite` to replace it", column.name)
+                ));
+            }
+            df = df.with_column(column.computation.make_expr()?.alias(&column.name));
+        }
+        Ok(df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::filter::clause::{Clause, FilterOperators};
+
+    fn make_df() -> DataFrame {
+        df!(
+            "first_name" => &["Alice", "Bob", "Charlie"],
+            "last_name" => &[Some("Smith"), None, Some("Brown")],
+            "price" => &[10.0, 20.0, 30.0],
+            "qty" => &[3, 1, 2]
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_can_compute_columns() {
+        let transformation = WithColumns {
+            columns: vec![
+                NewColumn {
+                    name: String::from("total"),
+                    computation: Computation::arithmetic(
+                        Computation::column("price"),
+                        Operator::Multiply,
+                        Computation::column("qty")
+                    )
+                },
+                NewColumn {
+                    name: String::from("size"),
+                    computation: Computation::When {
+                        branches: vec![
+                            WhenThen {
+                                condition: Condition::Clause(Clause {
+                                    column: String::from("total"),
+                                    operator: FilterOperators::PolarsOperator(Operator::GtEq, lit(60))
+                                }),
+                                then: Computation::Literal(LiteralValue::String("large".into()))
+                            },
+                            WhenThen {
+                                condition: Condition::Clause(Clause {
+                                    column: String::from("total"),
+                                    operator: FilterOperators::PolarsOperator(Operator::GtEq, lit(30))
+                                }),
+                                then: Computation::Literal(LiteralValue::String("medium".into()))
+                            },
+                        ],
+                        otherwise: Box::new(Computation::Literal(LiteralValue::String("small".into())))
+                    }
+                },
+                NewColumn {
+                    name: String::from("label"),
+                    computation: Computation::Concat {
+                        parts: vec![
+                            Computation::call(Function::Upper, Computation::column("first_name")),
+                            Computation::column("last_name"),
+                            Computation::column("qty"),
+                        ],
+                        separator: String::from(" ")
+                    }
+                },
+            ],
+            overwrite: false
+        };
+
+        let result = transformation.apply(make_df().lazy()).unwrap()
+            .select([col("total"), col("size"), col("label")])
+            .collect()
+            .unwrap();
+        let expected = df!(
+            "total" => &[30.0, 20.0, 60.0],
+            "size" => &["medium", "small", "large"],
+            "label" => &["ALICE Smith 3", "BOB 1", "CHARLIE Brown 2"]
+        ).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_overwrite_is_opt_in() {
+        let mut transformation = WithColumns {
+            columns: vec![NewColumn {
+                name: String::from("price"),
+                computation: Computation::call(
+                    Function::Round(0),
+                    Computation::arithmetic(Computation::column("price"), Operator::TrueDivide, Computation::Literal(LiteralValue::Float64(3.0)))
+                )
+            }],
+            overwrite: false
+        };
+
+        let result = transformation.apply(make_df().lazy());
+        assert!(matches!(result, Err(DiasError::InvalidParameter { step: "WithColumns", parameter: "columns", .. })));
+
+        transformation.overwrite = true;
+        let result = transformation.apply(make_df().lazy()).unwrap().collect().unwrap();
+        assert_eq!(result.column("price").unwrap().f64().unwrap().to_vec(), [Some(3.0), Some(7.0), Some(10.0)]);
+    }
+
+    #[test]
+    fn test_comparison_is_not_arithmetic() {
+        let transformation = WithColumns {
+            columns: vec![NewColumn {
+                name: String::from("flag"),
+                computation: Computation::arithmetic(Computation::column("price"), Operator::Gt, Computation::column("qty"))
+            }],
+            overwrite: false
+        };
+
+        let result = transformation.apply(make_df().lazy());
+
+        assert!(matches!(result, Err(DiasError::InvalidParameter { step: "WithColumns", parameter: "operator", .. })));
+    }
+}